@@ -0,0 +1,147 @@
+use crate::generic_param_replacer::GenericParamReplacer;
+use quote::ToTokens;
+
+/// Returns a path of the struct/enum that `alias` names, e.g. `RenderElement` for
+/// `type GlutinElement = RenderElement<GlesRenderer>;`.
+pub(crate) fn target_path(alias: &syn::ItemType) -> syn::Result<syn::Path> {
+    match alias.ty.as_ref() {
+        syn::Type::Path(type_path) if type_path.qself.is_none() => {
+            let mut path = type_path.path.clone();
+            path.segments.last_mut().unwrap().arguments = syn::PathArguments::None;
+            Ok(path)
+        }
+        _ => Err(syn::Error::new_spanned(
+            &alias.ty,
+            "expected a path to registered struct or enum",
+        )),
+    }
+}
+
+/// Converts a definition of struct/enum to the one of `alias`.
+///
+/// For example, for
+///
+/// ```text
+/// enum RenderElement<R> {
+///     Surface(SurfaceElement<R>),
+///     Cursor(CursorElement),
+/// }
+///
+/// type GlutinElement = RenderElement<GlesRenderer>;
+/// ```
+///
+/// it returns
+///
+/// ```text
+/// enum GlutinElement {
+///     Surface(SurfaceElement<GlesRenderer>),
+///     Cursor(CursorElement),
+/// }
+/// ```
+pub(crate) fn resolve_alias(
+    alias: &syn::ItemType,
+    mut structenum: syn::Item,
+) -> syn::Result<syn::Item> {
+    let syn::Type::Path(type_path) = alias.ty.as_ref() else {
+        panic!("alias should be checked in `#[thin_delegate::register]`");
+    };
+    let generic_args = &type_path.path.segments.last().unwrap().arguments;
+
+    let (ident, generics, fields) = match &mut structenum {
+        syn::Item::Struct(struct_) => (
+            &mut struct_.ident,
+            &mut struct_.generics,
+            struct_.fields.iter_mut().collect::<Vec<_>>(),
+        ),
+        syn::Item::Enum(enum_) => (
+            &mut enum_.ident,
+            &mut enum_.generics,
+            enum_
+                .variants
+                .iter_mut()
+                .flat_map(|variant| variant.fields.iter_mut())
+                .collect(),
+        ),
+        _ => panic!("structenum should be checked in `#[thin_delegate::register]`"),
+    };
+
+    let generic_param_replacer =
+        GenericParamReplacer::new(generics, generic_args).map_err(|_| {
+            syn::Error::new_spanned(
+                &alias.ty,
+                format!(
+                    "generic arguments don't match with the definition: {}",
+                    generics.to_token_stream(),
+                ),
+            )
+        })?;
+    for field in fields {
+        field.ty = generic_param_replacer.replace_type(field.ty.clone());
+    }
+    *ident = alias.ident.clone();
+    *generics = alias.generics.clone();
+
+    Ok(structenum)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use quote::quote;
+
+    #[test]
+    fn target_path() {
+        let alias = syn::parse2(quote! { type A = path::to::B<u8>; }).unwrap();
+        let expected: syn::Path = syn::parse2(quote! { path::to::B }).unwrap();
+        assert_eq!(super::target_path(&alias).unwrap(), expected);
+
+        let alias = syn::parse2(quote! { type A = <B as Trait>::C; }).unwrap();
+        assert!(super::target_path(&alias).is_err());
+
+        let alias = syn::parse2(quote! { type A = &'static B; }).unwrap();
+        assert!(super::target_path(&alias).is_err());
+    }
+
+    #[test]
+    fn resolve_alias_enum() {
+        let alias =
+            syn::parse2(quote! { type GlutinElement = RenderElement<GlesRenderer>; }).unwrap();
+        let structenum = syn::parse2(quote! {
+            enum RenderElement<R> {
+                Surface(SurfaceElement<R>),
+                Cursor(CursorElement),
+            }
+        })
+        .unwrap();
+        let expected: syn::Item = syn::parse2(quote! {
+            enum GlutinElement {
+                Surface(SurfaceElement<GlesRenderer>),
+                Cursor(CursorElement),
+            }
+        })
+        .unwrap();
+        assert_eq!(resolve_alias(&alias, structenum).unwrap(), expected);
+    }
+
+    #[test]
+    fn resolve_alias_struct_with_generics() {
+        let alias = syn::parse2(quote! { type Pair<'a, T> = Hoge<'a, T, u8>; }).unwrap();
+        let structenum = syn::parse2(quote! {
+            struct Hoge<'b, T, U> {
+                x: &'b (T, U),
+            }
+        })
+        .unwrap();
+        let expected: syn::Item = syn::parse2(quote! {
+            struct Pair<'a, T> {
+                x: &'a (T, u8),
+            }
+        })
+        .unwrap();
+        assert_eq!(resolve_alias(&alias, structenum).unwrap(), expected);
+
+        let alias = syn::parse2(quote! { type Pair = Hoge<u8>; }).unwrap();
+        let structenum = syn::parse2(quote! { struct Hoge<T, U>(T, U); }).unwrap();
+        assert!(resolve_alias(&alias, structenum).is_err());
+    }
+}
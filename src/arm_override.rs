@@ -0,0 +1,104 @@
+use std::collections::HashMap;
+
+/// A method qualified with `#[thin_delegate::arm(Pattern)]`, which gives an arm
+/// `Pattern => body` of the generated `match self`.
+pub(crate) struct ArmOverride {
+    pub pat: syn::Pat,
+    pub fn_: syn::ImplItemFn,
+}
+
+fn is_arm_attr(attr: &syn::Attribute) -> bool {
+    let idents = attr
+        .path()
+        .segments
+        .iter()
+        .map(|segment| segment.ident.to_string())
+        .collect::<Vec<_>>();
+    idents == ["thin_delegate", "arm"]
+}
+
+/// Removes methods qualified with `#[thin_delegate::arm(...)]` from `impl_` and returns them
+/// grouped by the method names.
+pub(crate) fn take_arm_overrides(
+    impl_: &mut syn::ItemImpl,
+) -> syn::Result<HashMap<syn::Ident, Vec<ArmOverride>>> {
+    let mut ret: HashMap<syn::Ident, Vec<ArmOverride>> = HashMap::new();
+    let mut items = vec![];
+    for item in std::mem::take(&mut impl_.items) {
+        let syn::ImplItem::Fn(mut fn_) = item else {
+            items.push(item);
+            continue;
+        };
+        let Some(i) = fn_.attrs.iter().position(is_arm_attr) else {
+            items.push(syn::ImplItem::Fn(fn_));
+            continue;
+        };
+        let attr = fn_.attrs.remove(i);
+        let pat = attr.parse_args_with(syn::Pat::parse_multi_with_leading_vert)?;
+        if fn_.sig.receiver().is_none() {
+            return Err(syn::Error::new_spanned(
+                &fn_.sig,
+                "`#[thin_delegate::arm]` requires a method with receiver",
+            ));
+        }
+        ret.entry(fn_.sig.ident.clone())
+            .or_default()
+            .push(ArmOverride { pat, fn_ });
+    }
+    impl_.items = items;
+
+    Ok(ret)
+}
+
+#[cfg(test)]
+mod tests {
+    use quote::quote;
+
+    #[test]
+    fn take_arm_overrides() {
+        let mut impl_: syn::ItemImpl = syn::parse2(quote! {
+            impl Hello for Animal {
+                #[thin_delegate::arm(Self::Cat(cat))]
+                fn hello(&self) -> String {
+                    cat.meow()
+                }
+
+                #[thin_delegate::arm(Self::Dog(_) | Self::Wolf(_))]
+                fn hello(&self) -> String {
+                    "bow".to_string()
+                }
+
+                fn bye(&self) -> String {
+                    "bye".to_string()
+                }
+            }
+        })
+        .unwrap();
+        let expected: syn::ItemImpl = syn::parse2(quote! {
+            impl Hello for Animal {
+                fn bye(&self) -> String {
+                    "bye".to_string()
+                }
+            }
+        })
+        .unwrap();
+        let overrides = super::take_arm_overrides(&mut impl_).unwrap();
+        assert_eq!(impl_, expected);
+        assert_eq!(overrides.len(), 1);
+        let hello = &overrides[&syn::parse_quote! { hello }];
+        assert_eq!(hello.len(), 2);
+        assert_eq!(hello[0].pat, syn::parse_quote! { Self::Cat(cat) });
+        assert!(hello[1].fn_.attrs.is_empty());
+
+        let mut impl_: syn::ItemImpl = syn::parse2(quote! {
+            impl Hello for Animal {
+                #[thin_delegate::arm(Self::Cat(cat))]
+                fn new() -> Self {
+                    todo!()
+                }
+            }
+        })
+        .unwrap();
+        assert!(super::take_arm_overrides(&mut impl_).is_err());
+    }
+}
@@ -1,7 +1,8 @@
 // FYI: See `exec_internal_fill_delegate()` first.
 
-use proc_macro2::{Span, TokenStream};
-use quote::quote;
+use crate::{alias_resolver, std_defs};
+use proc_macro2::{Group, Punct, Spacing, Span, TokenStream, TokenTree};
+use quote::{format_ident, quote, quote_spanned};
 
 fn macro_name_feed_trait_def_of<T>(trait_name: &T, span: Span, is_external: bool) -> syn::Ident
 where
@@ -14,12 +15,17 @@ where
     )
 }
 
-fn macro_name_feed_structenum_def_of<T>(structenum_name: &T, span: Span) -> syn::Ident
+fn macro_name_feed_structenum_def_of<T>(
+    structenum_name: &T,
+    span: Span,
+    is_external: bool,
+) -> syn::Ident
 where
     T: std::fmt::Display,
 {
+    let external = if is_external { "external_" } else { "" };
     syn::Ident::new(
-        &format!("__thin_delegate__feed_structenum_def_of_{structenum_name}",),
+        &format!("__thin_delegate__feed_structenum_def_of_{external}{structenum_name}",),
         span,
     )
 }
@@ -28,11 +34,20 @@ pub(crate) fn define_macro_feed_trait_def_of(
     ident: &syn::Ident,
     span: Span,
     is_external: bool,
+    export: bool,
     trait_: &syn::ItemTrait,
 ) -> TokenStream {
     let feed_trait_def_of = macro_name_feed_trait_def_of(ident, span, is_external);
-    quote! {
-        macro_rules! #feed_trait_def_of {
+    let trait_ = quote! { #trait_ };
+    let trait_ = if export {
+        replace_crate_with_dollar_crate(trait_)
+    } else {
+        trait_
+    };
+    define_macro(
+        &feed_trait_def_of,
+        export,
+        quote! {
             {
                 @KONT { $kont:path },
                 $(@$arg_key:ident { $arg_value:tt },)*
@@ -42,20 +57,28 @@ pub(crate) fn define_macro_feed_trait_def_of(
                     @TRAIT_DEF { #trait_ },
                 }
             }
-        }
-        #[allow(unused_imports)]
-        pub(crate) use #feed_trait_def_of;
-    }
+        },
+    )
 }
 
 pub(crate) fn define_macro_feed_structenum_def_of(
     ident: &syn::Ident,
     span: Span,
+    is_external: bool,
+    export: bool,
     structenum: &syn::Item,
 ) -> TokenStream {
-    let feed_structenum_def_of = macro_name_feed_structenum_def_of(ident, span);
-    quote! {
-        macro_rules! #feed_structenum_def_of {
+    let feed_structenum_def_of = macro_name_feed_structenum_def_of(ident, span, is_external);
+    let structenum = quote! { #structenum };
+    let structenum = if export {
+        replace_crate_with_dollar_crate(structenum)
+    } else {
+        structenum
+    };
+    define_macro(
+        &feed_structenum_def_of,
+        export,
+        quote! {
             {
                 @KONT { $kont:path },
                 $(@$arg_key:ident { $arg_value:tt },)*
@@ -65,72 +88,382 @@ pub(crate) fn define_macro_feed_structenum_def_of(
                     @STRUCTENUM_DEF { #structenum },
                 }
             }
+        },
+    )
+}
+
+/// Defines a macro for `type Alias = Target<...>;` that feeds the definition of `Target` converted
+/// to the one of `Alias`.
+///
+/// It calls the macro of `Target` with continuation `__internal__resolve_alias`, which does the
+/// conversion and calls the original continuation.
+pub(crate) fn define_macro_feed_structenum_def_of_alias(
+    ident: &syn::Ident,
+    span: Span,
+    export: bool,
+    alias: &syn::ItemType,
+    target_path: &syn::Path,
+) -> TokenStream {
+    let feed_structenum_def_of = macro_name_feed_structenum_def_of(ident, span, false);
+    let target_ident = &target_path.segments.last().unwrap().ident;
+    let feed_target_def_of = macro_path_next_to(
+        target_path,
+        macro_name_feed_structenum_def_of(target_ident, target_ident.span(), false),
+    );
+    let body = quote! {
+        #feed_target_def_of! {
+            @KONT { ::thin_delegate::__internal__resolve_alias },
+            @ALIAS_KONT { $kont },
+            @ALIAS_DEF {{ #alias }},
+            $(@$arg_key { $arg_value },)*
         }
+    };
+    let body = if export {
+        replace_crate_with_dollar_crate(body)
+    } else {
+        body
+    };
+    define_macro(
+        &feed_structenum_def_of,
+        export,
+        quote! {
+            {
+                @KONT { $kont:path },
+                $(@$arg_key:ident { $arg_value:tt },)*
+            } => {
+                #body
+            }
+        },
+    )
+}
+
+fn define_macro(name: &syn::Ident, export: bool, rules: TokenStream) -> TokenStream {
+    if !export {
+        return quote! {
+            macro_rules! #name {
+                #rules
+            }
+            #[allow(unused_imports)]
+            pub(crate) use #name;
+        };
+    }
+
+    // `#[macro_export]` puts a macro at the crate root. We give it a hidden unique name there and
+    // re-export it next to the registered item, so that `#[thin_delegate::fill_delegate]` in other
+    // crates can find it by the path of the item.
+    //
+    // The name must be stable across compilers and builds, so it is derived only from the crate
+    // name, the name of the item and its definition.
+    let crate_name = std::env::var("CARGO_PKG_NAME").unwrap_or_default();
+    let hash = fnv1a64(&[
+        crate_name.as_bytes(),
+        name.to_string().as_bytes(),
+        rules.to_string().as_bytes(),
+    ]);
+    let exported_name = syn::Ident::new(&format!("{name}__{hash:016x}"), name.span());
+    quote! {
+        #[doc(hidden)]
+        #[macro_export]
+        macro_rules! #exported_name {
+            #rules
+        }
+        #[doc(hidden)]
         #[allow(unused_imports)]
-        pub(crate) use #feed_structenum_def_of;
+        pub use #exported_name as #name;
+    }
+}
+
+/// FNV-1a hash of `parts`, each followed by a zero byte.
+///
+/// `std::hash::DefaultHasher` is not used because its algorithm may change between Rust releases.
+fn fnv1a64(parts: &[&[u8]]) -> u64 {
+    let mut hash = 0xcbf29ce484222325_u64;
+    for part in parts {
+        for &byte in part.iter().chain(&[0]) {
+            hash ^= u64::from(byte);
+            hash = hash.wrapping_mul(0x100000001b3);
+        }
+    }
+    hash
+}
+
+/// Replaces paths `crate::...` with `$crate::...`.
+///
+/// A definition fed by an exported macro is expanded in other crates. `$crate` makes paths in it
+/// point the crate that registered it.
+fn replace_crate_with_dollar_crate(tokens: TokenStream) -> TokenStream {
+    let tokens = tokens.into_iter().collect::<Vec<_>>();
+    let mut ret = TokenStream::new();
+    for (i, tt) in tokens.iter().enumerate() {
+        match tt {
+            TokenTree::Group(group) => {
+                let mut new_group = Group::new(
+                    group.delimiter(),
+                    replace_crate_with_dollar_crate(group.stream()),
+                );
+                new_group.set_span(group.span());
+                ret.extend([TokenTree::Group(new_group)]);
+            }
+            TokenTree::Ident(ident) if ident == "crate" => {
+                let is_path_root = matches!(
+                    (tokens.get(i + 1), tokens.get(i + 2)),
+                    (Some(TokenTree::Punct(p1)), Some(TokenTree::Punct(p2)))
+                        if p1.as_char() == ':' && p1.spacing() == Spacing::Joint && p2.as_char() == ':'
+                );
+                if is_path_root {
+                    let mut dollar = Punct::new('$', Spacing::Alone);
+                    dollar.set_span(ident.span());
+                    ret.extend([TokenTree::Punct(dollar)]);
+                }
+                ret.extend([tt.clone()]);
+            }
+            _ => {
+                ret.extend([tt.clone()]);
+            }
+        }
     }
+    ret
 }
 
+/// Returns a path of macro `macro_name` that is placed in the same module as the item `item_path`.
+fn macro_path_next_to(item_path: &syn::Path, macro_name: syn::Ident) -> syn::Path {
+    let mut path = item_path.clone();
+    *path.segments.last_mut().unwrap() = syn::PathSegment::from(macro_name);
+    path
+}
+
+/// Fills `impl_` for each `(structenum_path, impl_, span)` in `targets`, where errors of the target
+/// are reported at `span`. All impls must be ones of the trait `trait_path`, whose definition is
+/// fetched only once.
 pub(crate) fn exec_internal_fill_delegate(
-    trait_ident: &syn::Ident,
-    structenum_ident: &syn::Ident,
+    trait_path: &syn::Path,
+    targets: &[(&syn::Path, &syn::ItemImpl, Span)],
     external_trait_def: &Option<syn::Path>,
+    external_structenum_def: &Option<syn::Path>,
+    to_trait_to_verify: Option<&syn::Path>,
     args: TokenStream,
-    impl_: &syn::ItemImpl,
-) -> TokenStream {
+) -> syn::Result<TokenStream> {
+    let trait_ident = &trait_path.segments.last().unwrap().ident;
     let feed_trait_def_of = if let Some(external_trait_def) = &external_trait_def {
         let feed_trait_def_of =
             macro_name_feed_trait_def_of(&trait_ident, trait_ident.span(), true);
         quote! { #external_trait_def::#feed_trait_def_of }
     } else {
+        // The macro is defined (or re-exported) next to the trait. Searching it by the path of the
+        // trait allows to write `impl path::to::Trait for ...` without importing the macro, and to
+        // use traits registered with `#[thin_delegate::register(export)]` in other crates.
         let feed_trait_def_of =
             macro_name_feed_trait_def_of(&trait_ident, trait_ident.span(), false);
+        let feed_trait_def_of = macro_path_next_to(trait_path, feed_trait_def_of);
         quote! { #feed_trait_def_of }
     };
-    let feed_structenum_def_of =
-        macro_name_feed_structenum_def_of(&structenum_ident, structenum_ident.span());
+    let impl_keys = (0..targets.len())
+        .map(|i| format_ident!("IMPL_{}", i))
+        .collect::<Vec<_>>();
+    let impl_vars = (0..targets.len())
+        .map(|i| format_ident!("impl_{}", i))
+        .collect::<Vec<_>>();
+    let impls = targets.iter().map(|(_, impl_, _)| impl_);
+    let impl_args = quote! {
+        #(@#impl_keys {{ #impls }},)*
+    };
+
+    // Bundled definitions are known here. Skip the first step and feed it directly.
+    let start = match external_trait_def {
+        Some(external_trait_def) if std_defs::module_name(external_trait_def).is_some() => {
+            let trait_ = std_defs::trait_def(external_trait_def, trait_ident)?;
+            quote! {
+                __thin_delegate__trampoline1! {
+                    #impl_args
+                    @TRAIT_DEF { #trait_ },
+                }
+            }
+        }
+        _ => quote! {
+            #feed_trait_def_of! {
+                @KONT { __thin_delegate__trampoline1 },
+                #impl_args
+            }
+        },
+    };
+
+    let mut feed_structenum_defs = vec![];
+    let mut trampoline2s = vec![];
+    for (i, ((structenum_path, _, span), impl_var)) in targets.iter().zip(&impl_vars).enumerate() {
+        let structenum_ident = &structenum_path.segments.last().unwrap().ident;
+        let feed_structenum_def_of = if let Some(external_structenum_def) = &external_structenum_def
+        {
+            let feed_structenum_def_of =
+                macro_name_feed_structenum_def_of(&structenum_ident, structenum_ident.span(), true);
+            quote! { #external_structenum_def::#feed_structenum_def_of }
+        } else {
+            let feed_structenum_def_of = macro_name_feed_structenum_def_of(
+                &structenum_ident,
+                structenum_ident.span(),
+                false,
+            );
+            let feed_structenum_def_of =
+                macro_path_next_to(structenum_path, feed_structenum_def_of);
+            quote! { #feed_structenum_def_of }
+        };
+        // Each target has its own continuations so that errors are reported at `span`.
+        let (trampoline2_name, trampoline3_name) = if i == 0 {
+            (
+                format_ident!("__thin_delegate__trampoline2"),
+                format_ident!("__thin_delegate__trampoline3"),
+            )
+        } else {
+            (
+                format_ident!("__thin_delegate__trampoline2_{}", i),
+                format_ident!("__thin_delegate__trampoline3_{}", i),
+            )
+        };
+        let fill_delegate_attr = quote_spanned! {*span=>
+            #[::thin_delegate::__internal__fill_delegate(#args)]
+        };
+        feed_structenum_defs.push(quote! {
+            #feed_structenum_def_of! {
+                @KONT { #trampoline2_name },
+                @IMPL {{ $#impl_var }},
+                @TRAIT_DEF { $trait_def },
+            }
+        });
+
+        // With `verify_to_trait = true`, the definition of `to_trait` is also collected by
+        // `#feed_to_trait_def_of!` and `__thin_delegate__trampoline3!`, and put before `$impl`.
+        trampoline2s.push(if let Some(to_trait) = to_trait_to_verify {
+            let to_trait_ident = &to_trait.segments.last().unwrap().ident;
+            let feed_to_trait_def_of = macro_path_next_to(
+                to_trait,
+                macro_name_feed_trait_def_of(&to_trait_ident, to_trait_ident.span(), false),
+            );
+            quote! {
+                macro_rules! #trampoline3_name {
+                    {
+                        @IMPL {{ $impl:item }},
+                        @TRAIT_DEF { $trait_def:item },
+                        @STRUCTENUM_DEF { $structenum_def:item },
+                        @TRAIT_DEF { $to_trait_def:item },
+                    } => {
+                        #fill_delegate_attr
+                        mod __thin_delegate__change_this_name {
+                            $trait_def
+
+                            $structenum_def
+
+                            $to_trait_def
+
+                            $impl
+                        }
+                    }
+                }
+
+                macro_rules! #trampoline2_name {
+                    {
+                        @IMPL {{ $impl:item }},
+                        @TRAIT_DEF { $trait_def:item },
+                        @STRUCTENUM_DEF { $structenum_def:item },
+                    } => {
+                        #feed_to_trait_def_of! {
+                            @KONT { #trampoline3_name },
+                            @IMPL {{ $impl }},
+                            @TRAIT_DEF { $trait_def },
+                            @STRUCTENUM_DEF { $structenum_def },
+                        }
+                    }
+                }
+            }
+        } else {
+            quote! {
+                macro_rules! #trampoline2_name {
+                    {
+                        @IMPL {{ $impl:item }},
+                        @TRAIT_DEF { $trait_def:item },
+                        @STRUCTENUM_DEF { $structenum_def:item },
+                    } => {
+                        #fill_delegate_attr
+                        mod __thin_delegate__change_this_name {
+                            $trait_def
+
+                            $structenum_def
+
+                            $impl
+                        }
+                    }
+                }
+            }
+        });
+    }
 
     // Collect trait and structenum defs by CPS:
     //
     //    #feed_trait_def_of!
     // -> __thin_delegate__trampoline1!
-    // -> #feed_structenum_def_of!
+    // -> #feed_structenum_def_of! (for each target)
     // -> __thin_delegate__trampoline2!
     // -> #[::thin_delegate::__internal__fill_delegate]
-    quote! {
-        macro_rules! __thin_delegate__trampoline2 {
-            {
-                @IMPL {{ $impl:item }},
-                @TRAIT_DEF { $trait_def:item },
-                @STRUCTENUM_DEF { $structenum_def:item },
-            } => {
-                #[::thin_delegate::__internal__fill_delegate(#args)]
-                mod __thin_delegate__change_this_name {
-                    $trait_def
-
-                    $structenum_def
-
-                    $impl
-                }
-            }
-        }
+    Ok(quote! {
+        #(#trampoline2s)*
 
         macro_rules! __thin_delegate__trampoline1 {
             {
-                @IMPL {{ $impl:item }},
+                #(@#impl_keys {{ $#impl_vars:item }},)*
                 @TRAIT_DEF { $trait_def:item },
             } => {
-                #feed_structenum_def_of! {
-                    @KONT { __thin_delegate__trampoline2 },
-                    @IMPL {{ $impl }},
-                    @TRAIT_DEF { $trait_def },
-                }
+                #(#feed_structenum_defs)*
             }
         }
 
-        #feed_trait_def_of! {
-            @KONT { __thin_delegate__trampoline1 },
-            @IMPL {{ #impl_ }},
+        #start
+    })
+}
+
+/// Arguments of continuations, i.e. `@KEY { value }, ...`.
+struct KontArgs(Vec<(syn::Ident, Group)>);
+
+impl syn::parse::Parse for KontArgs {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let mut args = vec![];
+        while !input.is_empty() {
+            input.parse::<syn::Token![@]>()?;
+            let key = input.parse::<syn::Ident>()?;
+            let value = input.parse::<Group>()?;
+            args.push((key, value));
+            if !input.is_empty() {
+                input.parse::<syn::Token![,]>()?;
+            }
         }
+        Ok(KontArgs(args))
+    }
+}
+
+impl KontArgs {
+    /// Removes the first argument with `key`.
+    fn take(&mut self, key: &str) -> Group {
+        let i = self.0.iter().position(|(k, _)| k == key).unwrap();
+        self.0.remove(i).1
     }
 }
+
+/// Continuation of macros defined by `define_macro_feed_structenum_def_of_alias()`.
+pub(crate) fn exec_internal_resolve_alias(input: TokenStream) -> syn::Result<TokenStream> {
+    // We'll use panic here as it is only used by this crate.
+
+    let mut args = syn::parse2::<KontArgs>(input).unwrap();
+    // Aliases of aliases nest. Outer ones come later.
+    let kont = syn::parse2::<syn::Path>(args.take("ALIAS_KONT").stream()).unwrap();
+    let alias = args.take("ALIAS_DEF").stream();
+    let alias = syn::parse2::<Group>(alias).unwrap().stream();
+    let alias = syn::parse2::<syn::ItemType>(alias).unwrap();
+    let structenum = syn::parse2::<syn::Item>(args.take("STRUCTENUM_DEF").stream()).unwrap();
+
+    let structenum = alias_resolver::resolve_alias(&alias, structenum)?;
+
+    let args = args.0.iter().map(|(key, value)| quote! { @#key #value });
+    Ok(quote! {
+        #kont! {
+            #(#args,)*
+            @STRUCTENUM_DEF { #structenum },
+        }
+    })
+}
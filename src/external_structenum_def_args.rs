@@ -0,0 +1,82 @@
+use syn::parse::Parse;
+
+mod kw {
+    syn::custom_keyword!(path);
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct ExternalStructEnumDefArgs {
+    pub path: Option<syn::Path>,
+}
+
+impl Parse for ExternalStructEnumDefArgs {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let mut this = ExternalStructEnumDefArgs { path: None };
+
+        let args =
+            syn::punctuated::Punctuated::<ParsableArg, syn::Token![,]>::parse_terminated(input)?;
+        for arg in args {
+            match arg {
+                ParsableArg::Path { path, .. } => {
+                    this.path = Some(path);
+                }
+            }
+        }
+
+        Ok(this)
+    }
+}
+
+#[derive(Debug)]
+enum ParsableArg {
+    Path {
+        #[allow(unused)]
+        path_kw: kw::path,
+        #[allow(unused)]
+        eq_token: syn::Token![=],
+        path: syn::Path,
+    },
+}
+
+impl Parse for ParsableArg {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let lookahead = input.lookahead1();
+        if lookahead.peek(kw::path) {
+            Ok(ParsableArg::Path {
+                path_kw: input.parse()?,
+                eq_token: input.parse()?,
+                path: input.parse()?,
+            })
+        } else {
+            Err(input.error("expected `path`"))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use quote::quote;
+    use syn::parse_quote;
+
+    #[test]
+    fn parsable() {
+        let input = quote! {};
+        let expected = ExternalStructEnumDefArgs { path: None };
+        assert_eq!(
+            syn::parse2::<ExternalStructEnumDefArgs>(input).unwrap(),
+            expected
+        );
+
+        let input = quote! { path = ::either::Either };
+        let expected = ExternalStructEnumDefArgs {
+            path: Some(parse_quote! { ::either::Either }),
+        };
+        assert_eq!(
+            syn::parse2::<ExternalStructEnumDefArgs>(input).unwrap(),
+            expected
+        );
+
+        assert!(syn::parse2::<ExternalStructEnumDefArgs>(quote! { hoge = hoge }).is_err());
+    }
+}
@@ -1,17 +1,27 @@
 use syn::parse::Parse;
 
 mod kw {
+    syn::custom_keyword!(crate_path);
+    syn::custom_keyword!(from_file);
+    syn::custom_keyword!(path);
+    syn::custom_keyword!(traits);
+    syn::custom_keyword!(verify);
     syn::custom_keyword!(with_uses);
 }
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Default, PartialEq, Eq)]
 pub(crate) struct ExternalTraitDefArgs {
     pub with_uses: bool,
+    pub verify: Option<syn::Path>,
+    pub path: Option<syn::Path>,
+    pub from_file: Option<syn::LitStr>,
+    pub traits: Vec<syn::Ident>,
+    pub crate_path: Option<syn::Path>,
 }
 
 impl Parse for ExternalTraitDefArgs {
     fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
-        let mut this = ExternalTraitDefArgs { with_uses: false };
+        let mut this = ExternalTraitDefArgs::default();
 
         let args =
             syn::punctuated::Punctuated::<ParsableArg, syn::Token![,]>::parse_terminated(input)?;
@@ -20,6 +30,21 @@ impl Parse for ExternalTraitDefArgs {
                 ParsableArg::WithUses { with_uses, .. } => {
                     this.with_uses = with_uses.value;
                 }
+                ParsableArg::Verify { path, .. } => {
+                    this.verify = Some(path);
+                }
+                ParsableArg::Path { path, .. } => {
+                    this.path = Some(path);
+                }
+                ParsableArg::FromFile { file, .. } => {
+                    this.from_file = Some(file);
+                }
+                ParsableArg::Traits { traits, .. } => {
+                    this.traits.extend(traits);
+                }
+                ParsableArg::CratePath { path, .. } => {
+                    this.crate_path = Some(path);
+                }
             }
         }
 
@@ -36,6 +61,41 @@ enum ParsableArg {
         eq_token: syn::Token![=],
         with_uses: syn::LitBool,
     },
+    Verify {
+        #[allow(unused)]
+        verify_kw: kw::verify,
+        #[allow(unused)]
+        eq_token: syn::Token![=],
+        path: syn::Path,
+    },
+    Path {
+        #[allow(unused)]
+        path_kw: kw::path,
+        #[allow(unused)]
+        eq_token: syn::Token![=],
+        path: syn::Path,
+    },
+    FromFile {
+        #[allow(unused)]
+        from_file_kw: kw::from_file,
+        #[allow(unused)]
+        eq_token: syn::Token![=],
+        file: syn::LitStr,
+    },
+    Traits {
+        #[allow(unused)]
+        traits_kw: kw::traits,
+        #[allow(unused)]
+        paren_token: syn::token::Paren,
+        traits: syn::punctuated::Punctuated<syn::Ident, syn::Token![,]>,
+    },
+    CratePath {
+        #[allow(unused)]
+        crate_path_kw: kw::crate_path,
+        #[allow(unused)]
+        eq_token: syn::Token![=],
+        path: syn::Path,
+    },
 }
 
 impl Parse for ParsableArg {
@@ -47,8 +107,41 @@ impl Parse for ParsableArg {
                 eq_token: input.parse()?,
                 with_uses: input.parse()?,
             })
+        } else if lookahead.peek(kw::verify) {
+            Ok(ParsableArg::Verify {
+                verify_kw: input.parse()?,
+                eq_token: input.parse()?,
+                path: input.parse()?,
+            })
+        } else if lookahead.peek(kw::path) {
+            Ok(ParsableArg::Path {
+                path_kw: input.parse()?,
+                eq_token: input.parse()?,
+                path: input.parse()?,
+            })
+        } else if lookahead.peek(kw::from_file) {
+            Ok(ParsableArg::FromFile {
+                from_file_kw: input.parse()?,
+                eq_token: input.parse()?,
+                file: input.parse()?,
+            })
+        } else if lookahead.peek(kw::traits) {
+            let content;
+            Ok(ParsableArg::Traits {
+                traits_kw: input.parse()?,
+                paren_token: syn::parenthesized!(content in input),
+                traits: content.parse_terminated(syn::Ident::parse, syn::Token![,])?,
+            })
+        } else if lookahead.peek(kw::crate_path) {
+            Ok(ParsableArg::CratePath {
+                crate_path_kw: input.parse()?,
+                eq_token: input.parse()?,
+                path: input.parse()?,
+            })
         } else {
-            Err(input.error("expected `with_uses`"))
+            Err(input.error(
+                "expected `with_uses`, `verify`, `path`, `from_file`, `traits` or `crate_path`",
+            ))
         }
     }
 }
@@ -57,18 +150,65 @@ impl Parse for ParsableArg {
 mod tests {
     use super::*;
     use quote::quote;
+    use syn::parse_quote;
 
     #[test]
     fn parsable() {
         let input = quote! {};
-        let expected = ExternalTraitDefArgs { with_uses: false };
+        let expected = ExternalTraitDefArgs {
+            with_uses: false,
+            ..Default::default()
+        };
         assert_eq!(
             syn::parse2::<ExternalTraitDefArgs>(input).unwrap(),
             expected
         );
 
         let input = quote! { with_uses = true };
-        let expected = ExternalTraitDefArgs { with_uses: true };
+        let expected = ExternalTraitDefArgs {
+            with_uses: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            syn::parse2::<ExternalTraitDefArgs>(input).unwrap(),
+            expected
+        );
+
+        let input = quote! { with_uses = true, verify = ::smithay::input::pointer };
+        let expected = ExternalTraitDefArgs {
+            with_uses: true,
+            verify: Some(parse_quote! { ::smithay::input::pointer }),
+            ..Default::default()
+        };
+        assert_eq!(
+            syn::parse2::<ExternalTraitDefArgs>(input).unwrap(),
+            expected
+        );
+
+        let input = quote! { path = ::std::io::Write };
+        let expected = ExternalTraitDefArgs {
+            path: Some(parse_quote! { ::std::io::Write }),
+            ..Default::default()
+        };
+        assert_eq!(
+            syn::parse2::<ExternalTraitDefArgs>(input).unwrap(),
+            expected
+        );
+
+        let input = quote! {
+            from_file = "vendor/smithay/src/input/pointer/mod.rs",
+            traits(PointerTarget, KeyboardTarget),
+            crate_path = ::smithay,
+        };
+        let expected = ExternalTraitDefArgs {
+            from_file: Some(parse_quote! { "vendor/smithay/src/input/pointer/mod.rs" }),
+            traits: vec![
+                parse_quote! { PointerTarget },
+                parse_quote! { KeyboardTarget },
+            ],
+            crate_path: Some(parse_quote! { ::smithay }),
+            ..Default::default()
+        };
         assert_eq!(
             syn::parse2::<ExternalTraitDefArgs>(input).unwrap(),
             expected
@@ -0,0 +1,343 @@
+/// Returns true if `attr` is `#[thin_delegate::delegate]` or `#[thin_delegate::delegate(...)]`.
+fn is_delegate_attr(attr: &syn::Attribute) -> bool {
+    let idents = attr
+        .path()
+        .segments
+        .iter()
+        .map(|segment| segment.ident.to_string())
+        .collect::<Vec<_>>();
+    idents == ["thin_delegate", "delegate"]
+}
+
+/// Returns true if `field` is marked as the delegation target for trait `trait_ident`.
+fn is_marked(field: &syn::Field, trait_ident: &syn::Ident) -> syn::Result<bool> {
+    for attr in field.attrs.iter().filter(|attr| is_delegate_attr(attr)) {
+        let traits = match &attr.meta {
+            syn::Meta::Path(_) => return Ok(true),
+            syn::Meta::List(meta) => meta.parse_args_with(
+                syn::punctuated::Punctuated::<syn::Path, syn::Token![,]>::parse_terminated,
+            )?,
+            syn::Meta::NameValue(_) => {
+                return Err(syn::Error::new_spanned(
+                    attr,
+                    "expected `#[thin_delegate::delegate]` or `#[thin_delegate::delegate(Trait, ...)]`",
+                ));
+            }
+        };
+        if traits
+            .iter()
+            .any(|path| path.segments.last().unwrap().ident == *trait_ident)
+        {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+/// Returns the field to delegate to for trait `trait_ident` and its member, i.e. the field marked
+/// with `#[thin_delegate::delegate]`, or the only field except for marker fields like
+/// `PhantomData<T>`. If all fields are marker fields, the only field is chosen.
+///
+/// Returns `None` if there is no natural choice.
+pub(crate) fn delegate_field<'a>(
+    fields: &'a syn::Fields,
+    trait_ident: &syn::Ident,
+) -> syn::Result<Option<(syn::Member, &'a syn::Field)>> {
+    let mut marked = None;
+    for (i, field) in fields.iter().enumerate() {
+        if !is_marked(field, trait_ident)? {
+            continue;
+        }
+        if marked.is_some() {
+            return Err(syn::Error::new_spanned(
+                field,
+                format!("multiple fields are marked with `#[thin_delegate::delegate]` for `{trait_ident}`"),
+            ));
+        }
+        marked = Some((member(i, field), field));
+    }
+    if marked.is_some() {
+        return Ok(marked);
+    }
+
+    let mut candidates = fields
+        .iter()
+        .enumerate()
+        .filter(|(_, field)| !is_marker_type(&field.ty));
+    match (candidates.next(), candidates.next()) {
+        (Some((i, field)), None) => Ok(Some((member(i, field), field))),
+        // The only field is used even if it is a marker field, e.g. `struct Hoge<T>(PhantomData<T>)`.
+        (None, _) if fields.len() == 1 => {
+            let field = fields.iter().next().unwrap();
+            Ok(Some((member(0, field), field)))
+        }
+        _ => Ok(None),
+    }
+}
+
+/// Returns the member of the `i`-th field `field`.
+pub(crate) fn member(i: usize, field: &syn::Field) -> syn::Member {
+    match &field.ident {
+        Some(ident) => syn::Member::Named(ident.clone()),
+        None => syn::Member::Unnamed(syn::Index::from(i)),
+    }
+}
+
+/// Returns true if `ty` is syntactically a zero-sized marker type, i.e. `PhantomData<..>`,
+/// `PhantomPinned` or `()`.
+pub(crate) fn is_marker_type(ty: &syn::Type) -> bool {
+    match ty {
+        syn::Type::Path(type_path) if type_path.qself.is_none() => {
+            let ident = &type_path.path.segments.last().unwrap().ident;
+            ident == "PhantomData" || ident == "PhantomPinned"
+        }
+        syn::Type::Tuple(tuple) => tuple.elems.is_empty(),
+        syn::Type::Paren(paren) => is_marker_type(&paren.elem),
+        _ => false,
+    }
+}
+
+/// Arguments of `#[thin_delegate::unit_variant(...)]`.
+#[derive(Debug, PartialEq)]
+pub(crate) enum UnitVariantExprs {
+    /// `#[thin_delegate::unit_variant(expr)]` for all methods.
+    All(syn::Expr),
+    /// `#[thin_delegate::unit_variant(method1 = expr1, method2 = expr2)]`.
+    PerMethod(Vec<(syn::Ident, syn::Expr)>),
+}
+
+impl UnitVariantExprs {
+    /// Returns the expression for method `method`.
+    pub fn get(&self, method: &syn::Ident) -> Option<&syn::Expr> {
+        match self {
+            UnitVariantExprs::All(expr) => Some(expr),
+            UnitVariantExprs::PerMethod(exprs) => exprs
+                .iter()
+                .find(|(ident, _)| ident == method)
+                .map(|(_, expr)| expr),
+        }
+    }
+}
+
+/// Returns the arguments of `#[thin_delegate::unit_variant(...)]` on `variant`.
+pub(crate) fn unit_variant_exprs(variant: &syn::Variant) -> syn::Result<Option<UnitVariantExprs>> {
+    let Some(attr) = variant.attrs.iter().find(|attr| is_unit_variant_attr(attr)) else {
+        return Ok(None);
+    };
+    let per_method = attr.parse_args_with(|input: syn::parse::ParseStream| {
+        syn::punctuated::Punctuated::<(syn::Ident, syn::Expr), syn::Token![,]>::parse_terminated_with(
+            input,
+            |input| {
+                let ident = input.parse::<syn::Ident>()?;
+                input.parse::<syn::Token![=]>()?;
+                Ok((ident, input.parse::<syn::Expr>()?))
+            },
+        )
+    });
+    match per_method {
+        Ok(exprs) if !exprs.is_empty() => Ok(Some(UnitVariantExprs::PerMethod(
+            exprs.into_iter().collect(),
+        ))),
+        _ => attr
+            .parse_args::<syn::Expr>()
+            .map(|expr| Some(UnitVariantExprs::All(expr))),
+    }
+}
+
+fn is_unit_variant_attr(attr: &syn::Attribute) -> bool {
+    let idents = attr
+        .path()
+        .segments
+        .iter()
+        .map(|segment| segment.ident.to_string())
+        .collect::<Vec<_>>();
+    idents == ["thin_delegate", "unit_variant"]
+}
+
+/// Returns true if `variant` is marked with `#[thin_delegate::constructor_variant]`.
+pub(crate) fn is_constructor_variant(variant: &syn::Variant) -> bool {
+    variant.attrs.iter().any(is_constructor_variant_attr)
+}
+
+fn is_constructor_variant_attr(attr: &syn::Attribute) -> bool {
+    let idents = attr
+        .path()
+        .segments
+        .iter()
+        .map(|segment| segment.ident.to_string())
+        .collect::<Vec<_>>();
+    idents == ["thin_delegate", "constructor_variant"]
+}
+
+/// Removes `#[thin_delegate::delegate]` on fields and `#[thin_delegate::unit_variant]` and
+/// `#[thin_delegate::constructor_variant]` on variants of struct/enum, which are only for
+/// `#[thin_delegate::fill_delegate]`.
+pub(crate) fn strip_helper_attrs(item: &mut syn::Item) {
+    let fields: Vec<&mut syn::Field> = match item {
+        syn::Item::Struct(struct_) => struct_.fields.iter_mut().collect(),
+        syn::Item::Enum(enum_) => {
+            for variant in &mut enum_.variants {
+                variant.attrs.retain(|attr| {
+                    !is_unit_variant_attr(attr) && !is_constructor_variant_attr(attr)
+                });
+            }
+            enum_
+                .variants
+                .iter_mut()
+                .flat_map(|variant| variant.fields.iter_mut())
+                .collect()
+        }
+        _ => return,
+    };
+    for field in fields {
+        field.attrs.retain(|attr| !is_delegate_attr(attr));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use quote::quote;
+    use syn::parse_quote;
+
+    #[test]
+    fn delegate_field() {
+        let hello: syn::Ident = parse_quote! { Hello };
+        let bye: syn::Ident = parse_quote! { Bye };
+
+        let struct_: syn::ItemStruct = syn::parse2(quote! {
+            struct Hoge(String);
+        })
+        .unwrap();
+        let (member, _) = super::delegate_field(&struct_.fields, &hello)
+            .unwrap()
+            .unwrap();
+        assert_eq!(member, parse_quote! { 0 });
+
+        let struct_: syn::ItemStruct = syn::parse2(quote! {
+            struct Hoge {
+                id: usize,
+                #[thin_delegate::delegate]
+                name: String,
+            }
+        })
+        .unwrap();
+        let (member, _) = super::delegate_field(&struct_.fields, &hello)
+            .unwrap()
+            .unwrap();
+        assert_eq!(member, parse_quote! { name });
+
+        let struct_: syn::ItemStruct = syn::parse2(quote! {
+            struct Hoge(
+                #[thin_delegate::delegate(Hello)] String,
+                #[thin_delegate::delegate(path::to::Bye)] char,
+                usize,
+            );
+        })
+        .unwrap();
+        let (member, _) = super::delegate_field(&struct_.fields, &hello)
+            .unwrap()
+            .unwrap();
+        assert_eq!(member, parse_quote! { 0 });
+        let (member, _) = super::delegate_field(&struct_.fields, &bye)
+            .unwrap()
+            .unwrap();
+        assert_eq!(member, parse_quote! { 1 });
+
+        let struct_: syn::ItemStruct = syn::parse2(quote! {
+            struct Hoge(String, usize);
+        })
+        .unwrap();
+        assert!(super::delegate_field(&struct_.fields, &hello)
+            .unwrap()
+            .is_none());
+
+        let struct_: syn::ItemStruct = syn::parse2(quote! {
+            struct Hoge<T>(PhantomPinned, String, ::core::marker::PhantomData<T>, ());
+        })
+        .unwrap();
+        let (member, _) = super::delegate_field(&struct_.fields, &hello)
+            .unwrap()
+            .unwrap();
+        assert_eq!(member, parse_quote! { 1 });
+
+        let struct_: syn::ItemStruct = syn::parse2(quote! {
+            struct Hoge<T>(PhantomData<T>);
+        })
+        .unwrap();
+        let (member, _) = super::delegate_field(&struct_.fields, &hello)
+            .unwrap()
+            .unwrap();
+        assert_eq!(member, parse_quote! { 0 });
+
+        let struct_: syn::ItemStruct = syn::parse2(quote! {
+            struct Hoge<T>(PhantomData<T>, ());
+        })
+        .unwrap();
+        assert!(super::delegate_field(&struct_.fields, &hello)
+            .unwrap()
+            .is_none());
+
+        let struct_: syn::ItemStruct = syn::parse2(quote! {
+            struct Hoge(
+                #[thin_delegate::delegate] String,
+                #[thin_delegate::delegate(Hello)] usize,
+            );
+        })
+        .unwrap();
+        assert!(super::delegate_field(&struct_.fields, &hello).is_err());
+        assert!(super::delegate_field(&struct_.fields, &bye).is_ok());
+    }
+
+    #[test]
+    fn unit_variant_exprs() {
+        let hello: syn::Ident = parse_quote! { hello };
+        let len: syn::Ident = parse_quote! { len };
+
+        let variant: syn::Variant = parse_quote! {
+            #[thin_delegate::unit_variant("nobody".into())]
+            Nobody
+        };
+        let exprs = super::unit_variant_exprs(&variant).unwrap().unwrap();
+        let expected: syn::Expr = parse_quote! { "nobody".into() };
+        assert_eq!(exprs, super::UnitVariantExprs::All(expected.clone()));
+        assert_eq!(exprs.get(&len), Some(&expected));
+
+        let variant: syn::Variant = parse_quote! {
+            #[thin_delegate::unit_variant(hello = "nobody".into(), len = 0)]
+            Nobody
+        };
+        let exprs = super::unit_variant_exprs(&variant).unwrap().unwrap();
+        assert_eq!(exprs.get(&hello), Some(&expected));
+        assert_eq!(exprs.get(&len), Some(&parse_quote! { 0 }));
+        assert_eq!(exprs.get(&parse_quote! { bye }), None);
+
+        let variant: syn::Variant = parse_quote! { Nobody };
+        assert_eq!(super::unit_variant_exprs(&variant).unwrap(), None);
+    }
+
+    #[test]
+    fn strip_helper_attrs() {
+        let mut item: syn::Item = syn::parse2(quote! {
+            enum Hoge {
+                #[thin_delegate::unit_variant(0)]
+                #[doc = "y"]
+                C,
+                #[thin_delegate::constructor_variant]
+                A(#[thin_delegate::delegate] String, #[doc = "x"] usize),
+                B { #[thin_delegate::delegate(Hello)] x: char, #[delegate] y: char },
+            }
+        })
+        .unwrap();
+        let expected: syn::Item = syn::parse2(quote! {
+            enum Hoge {
+                #[doc = "y"]
+                C,
+                A(String, #[doc = "x"] usize),
+                B { x: char, #[delegate] y: char },
+            }
+        })
+        .unwrap();
+        super::strip_helper_attrs(&mut item);
+        assert_eq!(item, expected);
+    }
+}
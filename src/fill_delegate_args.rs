@@ -1,127 +1,316 @@
 use proc_macro2::{Span, TokenStream};
 use quote::TokenStreamExt;
+use std::collections::HashSet;
 use syn::parse::Parse;
 
 mod kw {
+    syn::custom_keyword!(constructor_variant);
     syn::custom_keyword!(delegate_fn_with_default_impl);
+    syn::custom_keyword!(external_structenum_def);
     syn::custom_keyword!(external_trait_def);
+    syn::custom_keyword!(include);
+    syn::custom_keyword!(infer_bounds);
+    syn::custom_keyword!(map_arg);
+    syn::custom_keyword!(map_err);
+    syn::custom_keyword!(map_return);
+    syn::custom_keyword!(only);
+    syn::custom_keyword!(rename);
     syn::custom_keyword!(scheme);
+    syn::custom_keyword!(skip);
+    syn::custom_keyword!(to_trait);
+    syn::custom_keyword!(unit_variants);
+    syn::custom_keyword!(verify_to_trait);
+    syn::custom_keyword!(with_default_impl);
+    syn::custom_keyword!(with_supertraits);
 }
 
 #[derive(Debug, Default, PartialEq, Eq)]
 pub(crate) struct FillDelegateArgs {
     pub delegate_fn_with_default_impl: bool,
     pub external_trait_def: Option<syn::Path>,
+    pub external_structenum_def: Option<syn::Path>,
     pub scheme: Option<syn::ExprClosure>,
+    /// Schemes for specific methods, i.e. `scheme(method1, method2) = ...`.
+    pub method_schemes: Vec<(Vec<syn::Ident>, syn::ExprClosure)>,
+    pub unit_variants: Option<UnitVariants>,
+    /// Methods not to fill, i.e. `skip(method1, method2)`.
+    pub skip: Vec<syn::Ident>,
+    /// Methods to fill exclusively, i.e. `only(method1, method2)`.
+    pub only: Option<Vec<syn::Ident>>,
+    /// Methods with default implementation to fill, i.e. `with_default_impl(method1, method2)`.
+    pub with_default_impl: Vec<syn::Ident>,
+    /// Trait to call methods of the delegation target with, i.e. `to_trait = path::to::Trait`.
+    pub to_trait: Option<syn::Path>,
+    /// Methods of the delegation target to call, i.e. `rename(method = target_method)`.
+    pub renames: Vec<(syn::Ident, syn::Ident)>,
+    pub verify_to_trait: bool,
+    /// Conversion of return values, i.e. `map_return = Into::into`.
+    pub map_return: Option<syn::Expr>,
+    /// Conversion of errors of `Result` return values, i.e. `map_err = From::from`.
+    pub map_err: Option<syn::Expr>,
+    /// Conversions of arguments, i.e. `map_arg(arg1 = Into::into, arg2 = ...)`.
+    pub map_args: Vec<(syn::Ident, syn::Expr)>,
+    pub with_supertraits: bool,
+    /// Supertraits not to fill, i.e. `with_supertraits(skip(Trait1, Trait2))`.
+    pub skip_supertraits: Vec<syn::Ident>,
+    /// Auto traits and derivable traits to fill, i.e. `with_supertraits(include(Trait1, Trait2))`.
+    pub include_supertraits: Vec<syn::Ident>,
+    pub infer_bounds: bool,
+    /// Variant of enum that functions returning `Self` construct, i.e. `constructor_variant = X`.
+    pub constructor_variant: Option<syn::Ident>,
+}
+
+/// What methods do for unit variants of enum.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum UnitVariants {
+    Unreachable,
+    Panic,
+    Default,
 }
 
 impl FillDelegateArgs {
     pub fn validate(&self) -> syn::Result<()> {
-        let Some(scheme) = self.scheme.as_ref() else {
-            return Ok(());
-        };
+        if let Some(scheme) = &self.scheme {
+            validate_scheme(scheme)?;
+        }
 
-        if !scheme.attrs.is_empty() {
-            // This shouldn't happen because parsing of `syn::ExprClosure` requires that the stream
-            // starts with `|...|`.
-            panic!();
+        let mut methods = HashSet::new();
+        for (idents, scheme) in &self.method_schemes {
+            for ident in idents {
+                if !methods.insert(ident) {
+                    return Err(syn::Error::new_spanned(
+                        ident,
+                        format!("multiple schemes are given for `{ident}`"),
+                    ));
+                }
+            }
+            validate_scheme(scheme)?;
         }
 
-        if scheme.lifetimes.is_some() {
+        if let (Some(ident), Some(_)) = (self.skip.first(), &self.only) {
             return Err(syn::Error::new_spanned(
-                &scheme.lifetimes,
-                "scheme can't have lifetime",
+                ident,
+                "`skip` and `only` can't be used together",
             ));
         }
 
-        if scheme.constness.is_some() {
-            return Err(syn::Error::new_spanned(
-                scheme.constness,
-                "scheme can't have `const`",
-            ));
+        let mut methods = HashSet::new();
+        for (ident, _) in &self.renames {
+            if !methods.insert(ident) {
+                return Err(syn::Error::new_spanned(
+                    ident,
+                    format!("multiple renames are given for `{ident}`"),
+                ));
+            }
         }
 
-        if scheme.movability.is_some() {
-            return Err(syn::Error::new_spanned(
-                scheme.movability,
-                "scheme can't have `static`",
-            ));
+        let mut arg_idents = HashSet::new();
+        for (ident, _) in &self.map_args {
+            if !arg_idents.insert(ident) {
+                return Err(syn::Error::new_spanned(
+                    ident,
+                    format!("multiple conversions are given for `{ident}`"),
+                ));
+            }
         }
 
-        if scheme.asyncness.is_some() {
+        if let Some(ident) = self
+            .include_supertraits
+            .iter()
+            .find(|ident| self.skip_supertraits.contains(ident))
+        {
             return Err(syn::Error::new_spanned(
-                scheme.asyncness,
-                "scheme can't have `async`",
+                ident,
+                format!("`{ident}` is given to both `skip` and `include`"),
             ));
         }
 
-        if scheme.capture.is_some() {
-            return Err(syn::Error::new_spanned(
-                scheme.capture,
-                "scheme can't have `move`",
+        if self.verify_to_trait && self.to_trait.is_none() {
+            return Err(syn::Error::new(
+                Span::call_site(),
+                "`verify_to_trait` requires `to_trait`",
             ));
         }
 
-        if scheme.inputs.len() != 1 {
-            return Err(syn::Error::new_spanned(
-                &scheme.inputs,
-                "scheme must have an arg without type",
-            ));
+        Ok(())
+    }
+
+    /// Returns true if method `method` is selected by `skip`, `only` and `with_default_impl`.
+    ///
+    /// Methods listed in `only` are selected even if they have default implementation.
+    pub fn is_selected(&self, method: &syn::Ident, has_default_impl: bool) -> bool {
+        if self.skip.contains(method) {
+            return false;
         }
+        if let Some(only) = &self.only {
+            return only.contains(method);
+        }
+        !has_default_impl
+            || self.delegate_fn_with_default_impl
+            || self.with_default_impl.contains(method)
+    }
+
+    /// Returns true if method `method` is listed in `only` or `with_default_impl`.
+    pub fn is_explicitly_selected(&self, method: &syn::Ident) -> bool {
+        self.only.iter().flatten().any(|x| x == method) || self.with_default_impl.contains(method)
+    }
+
+    /// Returns methods designated by `skip`, `only` and `with_default_impl`.
+    pub fn selected_methods(&self) -> impl Iterator<Item = &syn::Ident> {
+        self.skip
+            .iter()
+            .chain(self.only.iter().flatten())
+            .chain(&self.with_default_impl)
+    }
+
+    /// Returns the arg and the body of the scheme for method `method`.
+    pub fn scheme_arg_and_body(&self, method: &syn::Ident) -> Option<(&syn::Ident, &syn::Expr)> {
+        let scheme = self
+            .method_schemes
+            .iter()
+            .find(|(idents, _)| idents.contains(method))
+            .map(|(_, scheme)| scheme)
+            .or(self.scheme.as_ref())?;
 
         let syn::Pat::Ident(arg) = &scheme.inputs[0] else {
-            return Err(syn::Error::new_spanned(
-                &scheme.inputs[0],
-                "scheme must have an arg without type",
-            ));
+            panic!();
         };
 
-        if !arg.attrs.is_empty() {
-            return Err(syn::Error::new_spanned(
-                arg,
-                "arg of scheme can't have attributes",
-            ));
-        }
+        Some((&arg.ident, &scheme.body))
+    }
 
-        if arg.by_ref.is_some() {
-            return Err(syn::Error::new_spanned(
-                arg.by_ref,
-                "arg of scheme can't have `ref`",
-            ));
-        }
+    /// Returns the method of the delegation target to call for method `method`.
+    pub fn renamed<'a>(&'a self, method: &'a syn::Ident) -> &'a syn::Ident {
+        self.renames
+            .iter()
+            .find(|(from, _)| from == method)
+            .map_or(method, |(_, to)| to)
+    }
 
-        if arg.mutability.is_some() {
-            return Err(syn::Error::new_spanned(
-                arg.mutability,
-                "arg of scheme can't have `mut`",
-            ));
+    /// Returns the trait to verify delegation against if `verify_to_trait = true`.
+    pub fn to_trait_to_verify(&self) -> Option<&syn::Path> {
+        self.to_trait.as_ref().filter(|_| self.verify_to_trait)
+    }
+
+    /// Returns the argument `arg` of the call to the delegation target, converted by `map_arg`.
+    pub fn map_arg(&self, arg: &syn::Ident) -> syn::Expr {
+        match self.map_args.iter().find(|(ident, _)| ident == arg) {
+            Some((_, f)) => syn::parse_quote! { (#f)(#arg) },
+            None => syn::parse_quote! { #arg },
         }
+    }
 
-        if arg.subpat.is_some() {
-            let subpat = arg.subpat.as_ref().unwrap();
-            let mut spans = TokenStream::new();
-            spans.append_all(Some(&subpat.0));
-            spans.append_all(Some(&subpat.1));
-            return Err(syn::Error::new_spanned(
-                spans,
-                "arg of scheme can't have `@ SUBPATTERN`",
-            ));
+    /// Returns `call` converted by `map_err` and `map_return` in this order.
+    ///
+    /// `map_err` is applied only if the method returns `Result`.
+    pub fn map_return(&self, call: TokenStream, returns_result: bool) -> TokenStream {
+        let call = match &self.map_err {
+            Some(f) if returns_result => {
+                quote::quote! { ::core::result::Result::map_err(#call, #f) }
+            }
+            _ => call,
+        };
+        match &self.map_return {
+            Some(f) => quote::quote! { (#f)(#call) },
+            None => call,
         }
+    }
 
-        Ok(())
+    /// Returns methods designated by `scheme(...)`.
+    pub fn scheme_methods(&self) -> impl Iterator<Item = &syn::Ident> {
+        self.method_schemes.iter().flat_map(|(idents, _)| idents)
     }
+}
 
-    pub fn scheme_arg_and_body(&self) -> Option<(&syn::Ident, &syn::Expr)> {
-        let Some(scheme) = &self.scheme else {
-            return None;
-        };
+fn validate_scheme(scheme: &syn::ExprClosure) -> syn::Result<()> {
+    if !scheme.attrs.is_empty() {
+        // This shouldn't happen because parsing of `syn::ExprClosure` requires that the stream
+        // starts with `|...|`.
+        panic!();
+    }
 
-        let syn::Pat::Ident(arg) = &scheme.inputs[0] else {
-            panic!();
-        };
+    if scheme.lifetimes.is_some() {
+        return Err(syn::Error::new_spanned(
+            &scheme.lifetimes,
+            "scheme can't have lifetime",
+        ));
+    }
 
-        Some((&arg.ident, &scheme.body))
+    if scheme.constness.is_some() {
+        return Err(syn::Error::new_spanned(
+            scheme.constness,
+            "scheme can't have `const`",
+        ));
     }
+
+    if scheme.movability.is_some() {
+        return Err(syn::Error::new_spanned(
+            scheme.movability,
+            "scheme can't have `static`",
+        ));
+    }
+
+    if scheme.asyncness.is_some() {
+        return Err(syn::Error::new_spanned(
+            scheme.asyncness,
+            "scheme can't have `async`",
+        ));
+    }
+
+    if scheme.capture.is_some() {
+        return Err(syn::Error::new_spanned(
+            scheme.capture,
+            "scheme can't have `move`",
+        ));
+    }
+
+    if scheme.inputs.len() != 1 {
+        return Err(syn::Error::new_spanned(
+            &scheme.inputs,
+            "scheme must have an arg without type",
+        ));
+    }
+
+    let syn::Pat::Ident(arg) = &scheme.inputs[0] else {
+        return Err(syn::Error::new_spanned(
+            &scheme.inputs[0],
+            "scheme must have an arg without type",
+        ));
+    };
+
+    if !arg.attrs.is_empty() {
+        return Err(syn::Error::new_spanned(
+            arg,
+            "arg of scheme can't have attributes",
+        ));
+    }
+
+    if arg.by_ref.is_some() {
+        return Err(syn::Error::new_spanned(
+            arg.by_ref,
+            "arg of scheme can't have `ref`",
+        ));
+    }
+
+    if arg.mutability.is_some() {
+        return Err(syn::Error::new_spanned(
+            arg.mutability,
+            "arg of scheme can't have `mut`",
+        ));
+    }
+
+    if arg.subpat.is_some() {
+        let subpat = arg.subpat.as_ref().unwrap();
+        let mut spans = TokenStream::new();
+        spans.append_all(Some(&subpat.0));
+        spans.append_all(Some(&subpat.1));
+        return Err(syn::Error::new_spanned(
+            spans,
+            "arg of scheme can't have `@ SUBPATTERN`",
+        ));
+    }
+
+    Ok(())
 }
 
 impl Parse for FillDelegateArgs {
@@ -141,9 +330,79 @@ impl Parse for FillDelegateArgs {
                 ParsableArg::ExternalTraitDef { path, .. } => {
                     this.external_trait_def = Some(path);
                 }
+                ParsableArg::ExternalStructEnumDef { path, .. } => {
+                    this.external_structenum_def = Some(path);
+                }
                 ParsableArg::Scheme { closure, .. } => {
                     this.scheme = Some(closure);
                 }
+                ParsableArg::MethodScheme {
+                    methods, closure, ..
+                } => {
+                    this.method_schemes
+                        .push((methods.into_iter().collect(), closure));
+                }
+                ParsableArg::UnitVariants { unit_variants, .. } => {
+                    this.unit_variants = Some(unit_variants);
+                }
+                ParsableArg::Skip { methods, .. } => {
+                    this.skip.extend(methods);
+                }
+                ParsableArg::Only { methods, .. } => {
+                    this.only.get_or_insert_with(Vec::new).extend(methods);
+                }
+                ParsableArg::WithDefaultImpl { methods, .. } => {
+                    this.with_default_impl.extend(methods);
+                }
+                ParsableArg::ToTrait { path, .. } => {
+                    this.to_trait = Some(path);
+                }
+                ParsableArg::Rename { renames, .. } => {
+                    this.renames
+                        .extend(renames.into_iter().map(|rename| (rename.from, rename.to)));
+                }
+                ParsableArg::MapReturn { expr, .. } => {
+                    this.map_return = Some(expr);
+                }
+                ParsableArg::MapErr { expr, .. } => {
+                    this.map_err = Some(expr);
+                }
+                ParsableArg::MapArg { map_args, .. } => {
+                    this.map_args.extend(
+                        map_args
+                            .into_iter()
+                            .map(|map_arg| (map_arg.ident, map_arg.expr)),
+                    );
+                }
+                ParsableArg::VerifyToTrait {
+                    verify_to_trait, ..
+                } => {
+                    this.verify_to_trait = verify_to_trait.value;
+                }
+                ParsableArg::WithSupertraits {
+                    with_supertraits, ..
+                } => {
+                    this.with_supertraits = with_supertraits.value;
+                }
+                ParsableArg::WithSupertraitsSelections { selections, .. } => {
+                    this.with_supertraits = true;
+                    for selection in selections {
+                        match selection {
+                            SupertraitSelection::Skip { supertraits, .. } => {
+                                this.skip_supertraits.extend(supertraits);
+                            }
+                            SupertraitSelection::Include { supertraits, .. } => {
+                                this.include_supertraits.extend(supertraits);
+                            }
+                        }
+                    }
+                }
+                ParsableArg::InferBounds { infer_bounds, .. } => {
+                    this.infer_bounds = infer_bounds.value;
+                }
+                ParsableArg::ConstructorVariant { ident, .. } => {
+                    this.constructor_variant = Some(ident);
+                }
             }
         }
 
@@ -167,6 +426,13 @@ enum ParsableArg {
         eq_token: syn::Token![=],
         path: syn::Path,
     },
+    ExternalStructEnumDef {
+        #[allow(unused)]
+        external_structenum_def_kw: kw::external_structenum_def,
+        #[allow(unused)]
+        eq_token: syn::Token![=],
+        path: syn::Path,
+    },
     Scheme {
         #[allow(unused)]
         scheme_kw: kw::scheme,
@@ -174,6 +440,208 @@ enum ParsableArg {
         eq_token: syn::Token![=],
         closure: syn::ExprClosure,
     },
+    MethodScheme {
+        #[allow(unused)]
+        scheme_kw: kw::scheme,
+        #[allow(unused)]
+        paren_token: syn::token::Paren,
+        methods: syn::punctuated::Punctuated<syn::Ident, syn::Token![,]>,
+        #[allow(unused)]
+        eq_token: syn::Token![=],
+        closure: syn::ExprClosure,
+    },
+    UnitVariants {
+        #[allow(unused)]
+        unit_variants_kw: kw::unit_variants,
+        #[allow(unused)]
+        eq_token: syn::Token![=],
+        unit_variants: UnitVariants,
+    },
+    Skip {
+        #[allow(unused)]
+        skip_kw: kw::skip,
+        #[allow(unused)]
+        paren_token: syn::token::Paren,
+        methods: syn::punctuated::Punctuated<syn::Ident, syn::Token![,]>,
+    },
+    Only {
+        #[allow(unused)]
+        only_kw: kw::only,
+        #[allow(unused)]
+        paren_token: syn::token::Paren,
+        methods: syn::punctuated::Punctuated<syn::Ident, syn::Token![,]>,
+    },
+    WithDefaultImpl {
+        #[allow(unused)]
+        with_default_impl_kw: kw::with_default_impl,
+        #[allow(unused)]
+        paren_token: syn::token::Paren,
+        methods: syn::punctuated::Punctuated<syn::Ident, syn::Token![,]>,
+    },
+    ToTrait {
+        #[allow(unused)]
+        to_trait_kw: kw::to_trait,
+        #[allow(unused)]
+        eq_token: syn::Token![=],
+        path: syn::Path,
+    },
+    Rename {
+        #[allow(unused)]
+        rename_kw: kw::rename,
+        #[allow(unused)]
+        paren_token: syn::token::Paren,
+        renames: syn::punctuated::Punctuated<Rename, syn::Token![,]>,
+    },
+    VerifyToTrait {
+        #[allow(unused)]
+        verify_to_trait_kw: kw::verify_to_trait,
+        #[allow(unused)]
+        eq_token: syn::Token![=],
+        verify_to_trait: syn::LitBool,
+    },
+    WithSupertraits {
+        #[allow(unused)]
+        with_supertraits_kw: kw::with_supertraits,
+        #[allow(unused)]
+        eq_token: syn::Token![=],
+        with_supertraits: syn::LitBool,
+    },
+    WithSupertraitsSelections {
+        #[allow(unused)]
+        with_supertraits_kw: kw::with_supertraits,
+        #[allow(unused)]
+        paren_token: syn::token::Paren,
+        selections: syn::punctuated::Punctuated<SupertraitSelection, syn::Token![,]>,
+    },
+    InferBounds {
+        #[allow(unused)]
+        infer_bounds_kw: kw::infer_bounds,
+        #[allow(unused)]
+        eq_token: syn::Token![=],
+        infer_bounds: syn::LitBool,
+    },
+    ConstructorVariant {
+        #[allow(unused)]
+        constructor_variant_kw: kw::constructor_variant,
+        #[allow(unused)]
+        eq_token: syn::Token![=],
+        ident: syn::Ident,
+    },
+    MapReturn {
+        #[allow(unused)]
+        map_return_kw: kw::map_return,
+        #[allow(unused)]
+        eq_token: syn::Token![=],
+        expr: syn::Expr,
+    },
+    MapErr {
+        #[allow(unused)]
+        map_err_kw: kw::map_err,
+        #[allow(unused)]
+        eq_token: syn::Token![=],
+        expr: syn::Expr,
+    },
+    MapArg {
+        #[allow(unused)]
+        map_arg_kw: kw::map_arg,
+        #[allow(unused)]
+        paren_token: syn::token::Paren,
+        map_args: syn::punctuated::Punctuated<MapArg, syn::Token![,]>,
+    },
+}
+
+/// `method = target_method` in `rename(...)`.
+#[derive(Debug)]
+struct Rename {
+    from: syn::Ident,
+    #[allow(unused)]
+    eq_token: syn::Token![=],
+    to: syn::Ident,
+}
+
+/// `skip(...)` or `include(...)` in `with_supertraits(...)`.
+#[derive(Debug)]
+enum SupertraitSelection {
+    Skip {
+        #[allow(unused)]
+        skip_kw: kw::skip,
+        #[allow(unused)]
+        paren_token: syn::token::Paren,
+        supertraits: syn::punctuated::Punctuated<syn::Ident, syn::Token![,]>,
+    },
+    Include {
+        #[allow(unused)]
+        include_kw: kw::include,
+        #[allow(unused)]
+        paren_token: syn::token::Paren,
+        supertraits: syn::punctuated::Punctuated<syn::Ident, syn::Token![,]>,
+    },
+}
+
+impl Parse for SupertraitSelection {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let lookahead = input.lookahead1();
+        let content;
+        if lookahead.peek(kw::skip) {
+            Ok(SupertraitSelection::Skip {
+                skip_kw: input.parse()?,
+                paren_token: syn::parenthesized!(content in input),
+                supertraits: content.parse_terminated(syn::Ident::parse, syn::Token![,])?,
+            })
+        } else if lookahead.peek(kw::include) {
+            Ok(SupertraitSelection::Include {
+                include_kw: input.parse()?,
+                paren_token: syn::parenthesized!(content in input),
+                supertraits: content.parse_terminated(syn::Ident::parse, syn::Token![,])?,
+            })
+        } else {
+            Err(lookahead.error())
+        }
+    }
+}
+
+/// `arg = expr` in `map_arg(...)`.
+#[derive(Debug)]
+struct MapArg {
+    ident: syn::Ident,
+    #[allow(unused)]
+    eq_token: syn::Token![=],
+    expr: syn::Expr,
+}
+
+impl Parse for MapArg {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        Ok(MapArg {
+            ident: input.parse()?,
+            eq_token: input.parse()?,
+            expr: input.parse()?,
+        })
+    }
+}
+
+impl Parse for Rename {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        Ok(Rename {
+            from: input.parse()?,
+            eq_token: input.parse()?,
+            to: input.parse()?,
+        })
+    }
+}
+
+impl Parse for UnitVariants {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let ident = input.parse::<syn::Ident>()?;
+        match ident.to_string().as_str() {
+            "unreachable" => Ok(UnitVariants::Unreachable),
+            "panic" => Ok(UnitVariants::Panic),
+            "default" => Ok(UnitVariants::Default),
+            _ => Err(syn::Error::new_spanned(
+                ident,
+                "expected `unreachable`, `panic` or `default`",
+            )),
+        }
+    }
 }
 
 impl Parse for ParsableArg {
@@ -191,12 +659,117 @@ impl Parse for ParsableArg {
                 eq_token: input.parse()?,
                 path: input.parse()?,
             })
+        } else if lookahead.peek(kw::external_structenum_def) {
+            Ok(ParsableArg::ExternalStructEnumDef {
+                external_structenum_def_kw: input.parse()?,
+                eq_token: input.parse()?,
+                path: input.parse()?,
+            })
+        } else if lookahead.peek(kw::scheme) && input.peek2(syn::token::Paren) {
+            let content;
+            Ok(ParsableArg::MethodScheme {
+                scheme_kw: input.parse()?,
+                paren_token: syn::parenthesized!(content in input),
+                methods: content.parse_terminated(syn::Ident::parse, syn::Token![,])?,
+                eq_token: input.parse()?,
+                closure: input.parse()?,
+            })
         } else if lookahead.peek(kw::scheme) {
             Ok(ParsableArg::Scheme {
                 scheme_kw: input.parse()?,
                 eq_token: input.parse()?,
                 closure: input.parse()?,
             })
+        } else if lookahead.peek(kw::unit_variants) {
+            Ok(ParsableArg::UnitVariants {
+                unit_variants_kw: input.parse()?,
+                eq_token: input.parse()?,
+                unit_variants: input.parse()?,
+            })
+        } else if lookahead.peek(kw::skip) {
+            let content;
+            Ok(ParsableArg::Skip {
+                skip_kw: input.parse()?,
+                paren_token: syn::parenthesized!(content in input),
+                methods: content.parse_terminated(syn::Ident::parse, syn::Token![,])?,
+            })
+        } else if lookahead.peek(kw::only) {
+            let content;
+            Ok(ParsableArg::Only {
+                only_kw: input.parse()?,
+                paren_token: syn::parenthesized!(content in input),
+                methods: content.parse_terminated(syn::Ident::parse, syn::Token![,])?,
+            })
+        } else if lookahead.peek(kw::with_default_impl) {
+            let content;
+            Ok(ParsableArg::WithDefaultImpl {
+                with_default_impl_kw: input.parse()?,
+                paren_token: syn::parenthesized!(content in input),
+                methods: content.parse_terminated(syn::Ident::parse, syn::Token![,])?,
+            })
+        } else if lookahead.peek(kw::to_trait) {
+            Ok(ParsableArg::ToTrait {
+                to_trait_kw: input.parse()?,
+                eq_token: input.parse()?,
+                path: input.parse()?,
+            })
+        } else if lookahead.peek(kw::rename) {
+            let content;
+            Ok(ParsableArg::Rename {
+                rename_kw: input.parse()?,
+                paren_token: syn::parenthesized!(content in input),
+                renames: content.parse_terminated(Rename::parse, syn::Token![,])?,
+            })
+        } else if lookahead.peek(kw::map_return) {
+            Ok(ParsableArg::MapReturn {
+                map_return_kw: input.parse()?,
+                eq_token: input.parse()?,
+                expr: input.parse()?,
+            })
+        } else if lookahead.peek(kw::map_err) {
+            Ok(ParsableArg::MapErr {
+                map_err_kw: input.parse()?,
+                eq_token: input.parse()?,
+                expr: input.parse()?,
+            })
+        } else if lookahead.peek(kw::map_arg) {
+            let content;
+            Ok(ParsableArg::MapArg {
+                map_arg_kw: input.parse()?,
+                paren_token: syn::parenthesized!(content in input),
+                map_args: content.parse_terminated(MapArg::parse, syn::Token![,])?,
+            })
+        } else if lookahead.peek(kw::verify_to_trait) {
+            Ok(ParsableArg::VerifyToTrait {
+                verify_to_trait_kw: input.parse()?,
+                eq_token: input.parse()?,
+                verify_to_trait: input.parse()?,
+            })
+        } else if lookahead.peek(kw::with_supertraits) && input.peek2(syn::token::Paren) {
+            let content;
+            Ok(ParsableArg::WithSupertraitsSelections {
+                with_supertraits_kw: input.parse()?,
+                paren_token: syn::parenthesized!(content in input),
+                selections: content.parse_terminated(SupertraitSelection::parse, syn::Token![,])?,
+            })
+        } else if lookahead.peek(kw::with_supertraits) {
+            Ok(ParsableArg::WithSupertraits {
+                with_supertraits_kw: input.parse()?,
+                eq_token: input.parse()?,
+                with_supertraits: input.parse()?,
+            })
+        } else if lookahead.peek(kw::infer_bounds) {
+            Ok(ParsableArg::InferBounds {
+                infer_bounds_kw: input.parse()?,
+                eq_token: input.parse()?,
+                infer_bounds: input.parse()?,
+            })
+        } else if lookahead.peek(kw::constructor_variant) {
+            Ok(ParsableArg::ConstructorVariant {
+                constructor_variant_kw: input.parse()?,
+                eq_token: input.parse()?,
+                ident: input.parse()?,
+            })
         } else {
             Err(syn::Error::new(Span::call_site(), "error"))
         }
@@ -216,6 +789,7 @@ mod tests {
             delegate_fn_with_default_impl: false,
             external_trait_def: None,
             scheme: None,
+            ..Default::default()
         };
         assert_eq!(syn::parse2::<FillDelegateArgs>(input).unwrap(), expected);
 
@@ -224,6 +798,7 @@ mod tests {
             delegate_fn_with_default_impl: false,
             external_trait_def: Some(parse_quote! { __external_trait_def }),
             scheme: None,
+            ..Default::default()
         };
         assert_eq!(syn::parse2::<FillDelegateArgs>(input).unwrap(), expected);
 
@@ -232,6 +807,7 @@ mod tests {
             delegate_fn_with_default_impl: true,
             external_trait_def: None,
             scheme: None,
+            ..Default::default()
         };
         assert_eq!(syn::parse2::<FillDelegateArgs>(input).unwrap(), expected);
 
@@ -240,6 +816,7 @@ mod tests {
             delegate_fn_with_default_impl: false,
             external_trait_def: None,
             scheme: Some(parse_quote! { |f| f(&self.0.key()) }),
+            ..Default::default()
         };
         assert_eq!(syn::parse2::<FillDelegateArgs>(input).unwrap(), expected);
 
@@ -249,9 +826,126 @@ mod tests {
             delegate_fn_with_default_impl: false,
             external_trait_def: Some(parse_quote! { __external_trait_def }),
             scheme: Some(parse_quote! { |f| f(&self.0.key()) }),
+            ..Default::default()
+        };
+        assert_eq!(syn::parse2::<FillDelegateArgs>(input).unwrap(), expected);
+
+        let input = quote! { external_structenum_def = __external_structenum_def };
+        let expected = FillDelegateArgs {
+            external_structenum_def: Some(parse_quote! { __external_structenum_def }),
+            ..Default::default()
+        };
+        assert_eq!(syn::parse2::<FillDelegateArgs>(input).unwrap(), expected);
+
+        let input = quote! {
+            scheme(enter, leave) = |f| f(&self.0.wl_surface()),
+            scheme = |f| f(&self.0),
+        };
+        let expected = FillDelegateArgs {
+            scheme: Some(parse_quote! { |f| f(&self.0) }),
+            method_schemes: vec![(
+                vec![parse_quote! { enter }, parse_quote! { leave }],
+                parse_quote! { |f| f(&self.0.wl_surface()) },
+            )],
+            ..Default::default()
+        };
+        assert_eq!(syn::parse2::<FillDelegateArgs>(input).unwrap(), expected);
+
+        let input = quote! { unit_variants = default };
+        let expected = FillDelegateArgs {
+            unit_variants: Some(UnitVariants::Default),
+            ..Default::default()
+        };
+        assert_eq!(syn::parse2::<FillDelegateArgs>(input).unwrap(), expected);
+
+        let input = quote! { skip(hello), with_default_impl(bye, hoge) };
+        let expected = FillDelegateArgs {
+            skip: vec![parse_quote! { hello }],
+            with_default_impl: vec![parse_quote! { bye }, parse_quote! { hoge }],
+            ..Default::default()
+        };
+        assert_eq!(syn::parse2::<FillDelegateArgs>(input).unwrap(), expected);
+
+        let input = quote! { only(hello, bye) };
+        let expected = FillDelegateArgs {
+            only: Some(vec![parse_quote! { hello }, parse_quote! { bye }]),
+            ..Default::default()
+        };
+        assert_eq!(syn::parse2::<FillDelegateArgs>(input).unwrap(), expected);
+
+        let input = quote! {
+            to_trait = other::RenderLegacy,
+            rename(render = draw_frame, size = frame_size),
+            verify_to_trait = true,
+        };
+        let expected = FillDelegateArgs {
+            to_trait: Some(parse_quote! { other::RenderLegacy }),
+            renames: vec![
+                (parse_quote! { render }, parse_quote! { draw_frame }),
+                (parse_quote! { size }, parse_quote! { frame_size }),
+            ],
+            verify_to_trait: true,
+            ..Default::default()
+        };
+        assert_eq!(syn::parse2::<FillDelegateArgs>(input).unwrap(), expected);
+
+        let input = quote! {
+            map_return = Into::into,
+            map_err = |e| Error::Inner(e),
+            map_arg(size = Into::into, scale = |x| x as f64),
+        };
+        let expected = FillDelegateArgs {
+            map_return: Some(parse_quote! { Into::into }),
+            map_err: Some(parse_quote! { |e| Error::Inner(e) }),
+            map_args: vec![
+                (parse_quote! { size }, parse_quote! { Into::into }),
+                (parse_quote! { scale }, parse_quote! { |x| x as f64 }),
+            ],
+            ..Default::default()
+        };
+        assert_eq!(syn::parse2::<FillDelegateArgs>(input).unwrap(), expected);
+
+        let input = quote! { with_supertraits = true };
+        let expected = FillDelegateArgs {
+            with_supertraits: true,
+            ..Default::default()
+        };
+        assert_eq!(syn::parse2::<FillDelegateArgs>(input).unwrap(), expected);
+
+        let input = quote! { with_supertraits(skip(Display, Named)) };
+        let expected = FillDelegateArgs {
+            with_supertraits: true,
+            skip_supertraits: vec![parse_quote! { Display }, parse_quote! { Named }],
+            ..Default::default()
+        };
+        assert_eq!(syn::parse2::<FillDelegateArgs>(input).unwrap(), expected);
+
+        let input = quote! { with_supertraits(include(Debug), skip(Named)) };
+        let expected = FillDelegateArgs {
+            with_supertraits: true,
+            skip_supertraits: vec![parse_quote! { Named }],
+            include_supertraits: vec![parse_quote! { Debug }],
+            ..Default::default()
         };
         assert_eq!(syn::parse2::<FillDelegateArgs>(input).unwrap(), expected);
 
+        let input = quote! { infer_bounds = true };
+        let expected = FillDelegateArgs {
+            infer_bounds: true,
+            ..Default::default()
+        };
+        assert_eq!(syn::parse2::<FillDelegateArgs>(input).unwrap(), expected);
+
+        let input = quote! { constructor_variant = Xdg };
+        let expected = FillDelegateArgs {
+            constructor_variant: Some(parse_quote! { Xdg }),
+            ..Default::default()
+        };
+        assert_eq!(syn::parse2::<FillDelegateArgs>(input).unwrap(), expected);
+
+        assert!(syn::parse2::<FillDelegateArgs>(quote! { unit_variants = hoge }).is_err());
+        assert!(syn::parse2::<FillDelegateArgs>(quote! { rename(render) }).is_err());
+        assert!(syn::parse2::<FillDelegateArgs>(quote! { skip = hello }).is_err());
         assert!(syn::parse2::<FillDelegateArgs>(quote! { hoge = hoge }).is_err());
         assert!(syn::parse2::<FillDelegateArgs>(quote! { external_trait_def }).is_err());
         assert!(syn::parse2::<FillDelegateArgs>(
@@ -333,5 +1027,40 @@ mod tests {
             quote! { scheme = |f @ Fuga| f(&self.key()) },
             Err("arg of scheme can't have `@ SUBPATTERN`")
         );
+
+        assert_validate_error!(
+            quote! { scheme(hello) = move |f| f(&self.key()) },
+            Err("scheme can't have `move`")
+        );
+
+        assert_validate_error!(
+            quote! { scheme(hello, bye) = |f| f(&self.0), scheme(hello) = |f| f(&self.1) },
+            Err("multiple schemes are given for `hello`")
+        );
+
+        assert_validate_error!(
+            quote! { with_supertraits(skip(Debug), include(Debug)) },
+            Err("`Debug` is given to both `skip` and `include`")
+        );
+
+        assert_validate_error!(
+            quote! { skip(hello), only(bye) },
+            Err("`skip` and `only` can't be used together")
+        );
+
+        assert_validate_error!(
+            quote! { rename(render = draw, render = draw_frame) },
+            Err("multiple renames are given for `render`")
+        );
+
+        assert_validate_error!(
+            quote! { verify_to_trait = true },
+            Err("`verify_to_trait` requires `to_trait`")
+        );
+
+        assert_validate_error!(
+            quote! { map_arg(size = Into::into, size = From::from) },
+            Err("multiple conversions are given for `size`")
+        );
     }
 }
@@ -0,0 +1,160 @@
+use crate::fill_delegate_args::FillDelegateArgs;
+use proc_macro2::TokenStream;
+use syn::parse::Parse;
+use syn::parse_quote;
+use syn::spanned::Spanned;
+
+/// Input of `thin_delegate::fill_delegate_for!`, e.g.
+///
+/// ```text
+/// #[fill_delegate(scheme = |f| f(&self.0))]
+/// impl<R: Renderer> RenderElement<R> for A, B, <T: RenderElement<R>> C<T>
+/// ```
+#[derive(Debug)]
+pub(crate) struct FillDelegateForInput {
+    /// Arguments of `#[thin_delegate::fill_delegate]` common to all targets.
+    pub args: TokenStream,
+    pub generics: syn::Generics,
+    pub trait_path: syn::Path,
+    pub targets: Vec<Target>,
+}
+
+/// `<T: Element> C<T>` in `fill_delegate_for!`, where `<...>` is optional.
+#[derive(Debug)]
+pub(crate) struct Target {
+    pub generics: syn::Generics,
+    pub ty: syn::Type,
+}
+
+impl FillDelegateForInput {
+    /// Returns `impl <Trait> for <Target> {}` for each target.
+    pub fn impls(&self) -> Vec<syn::ItemImpl> {
+        self.targets
+            .iter()
+            .map(|target| {
+                let mut generics = self.generics.clone();
+                generics.params.extend(target.generics.params.clone());
+                let (impl_generics, _, where_clause) = generics.split_for_impl();
+                let trait_path = &self.trait_path;
+                let ty = &target.ty;
+                parse_quote! {
+                    impl #impl_generics #trait_path for #ty #where_clause {}
+                }
+            })
+            .collect()
+    }
+}
+
+impl Parse for FillDelegateForInput {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let attrs = input.call(syn::Attribute::parse_outer)?;
+        let args = match attrs.as_slice() {
+            [] => TokenStream::new(),
+            [attr] if attr.path().segments.last().unwrap().ident == "fill_delegate" => {
+                match &attr.meta {
+                    syn::Meta::Path(_) => TokenStream::new(),
+                    meta => meta.require_list()?.tokens.clone(),
+                }
+            }
+            [attr, ..] => {
+                return Err(syn::Error::new(
+                    attr.span(),
+                    "expected only `#[fill_delegate(...)]`",
+                ))
+            }
+        };
+        // Check them early for better error messages.
+        syn::parse2::<FillDelegateArgs>(args.clone())?.validate()?;
+
+        input.parse::<syn::Token![impl]>()?;
+        let generics = if input.peek(syn::Token![<]) {
+            input.parse::<syn::Generics>()?
+        } else {
+            syn::Generics::default()
+        };
+        let trait_path = input.parse::<syn::Path>()?;
+        input.parse::<syn::Token![for]>()?;
+
+        let mut targets = vec![];
+        loop {
+            targets.push(input.parse::<Target>()?);
+            if input.is_empty() {
+                break;
+            }
+            if input.peek(syn::Token![;]) {
+                input.parse::<syn::Token![;]>()?;
+                break;
+            }
+            input.parse::<syn::Token![,]>()?;
+            if input.is_empty() {
+                break;
+            }
+        }
+        if !input.is_empty() {
+            return Err(input.error("unexpected tokens"));
+        }
+
+        Ok(FillDelegateForInput {
+            args,
+            generics,
+            trait_path,
+            targets,
+        })
+    }
+}
+
+impl Parse for Target {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let generics = if input.peek(syn::Token![<]) {
+            input.parse::<syn::Generics>()?
+        } else {
+            syn::Generics::default()
+        };
+        let ty = input.parse::<syn::Type>()?;
+
+        Ok(Target { generics, ty })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use quote::quote;
+
+    #[test]
+    fn parsable() {
+        let input = quote! {
+            #[fill_delegate(scheme = |f| f(&self.0))]
+            impl<R: Renderer> RenderElement<R> for A, B, <T: RenderElement<R>> C<T>,
+        };
+        let input = syn::parse2::<FillDelegateForInput>(input).unwrap();
+        assert_eq!(
+            input.args.to_string(),
+            quote! { scheme = |f| f(&self.0) }.to_string()
+        );
+        assert_eq!(input.trait_path, parse_quote! { RenderElement<R> });
+        assert_eq!(input.targets.len(), 3);
+
+        let impls = input.impls();
+        let expected: syn::ItemImpl = parse_quote! {
+            impl<R: Renderer> RenderElement<R> for A {}
+        };
+        assert_eq!(impls[0], expected);
+        let expected: syn::ItemImpl = parse_quote! {
+            impl<R: Renderer, T: RenderElement<R>> RenderElement<R> for C<T> {}
+        };
+        assert_eq!(impls[2], expected);
+    }
+
+    #[test]
+    fn unparsable() {
+        for input in [
+            quote! { impl Hello for },
+            quote! { impl Hello for A B },
+            quote! { #[foo] impl Hello for A },
+            quote! { impl Hello for A; B },
+        ] {
+            assert!(syn::parse2::<FillDelegateForInput>(input).is_err());
+        }
+    }
+}
@@ -0,0 +1,140 @@
+use crate::fill_delegate_args::FillDelegateArgs;
+use proc_macro2::TokenStream;
+use syn::parse::Parse;
+
+mod kw {
+    syn::custom_keyword!(traits);
+}
+
+#[derive(Debug)]
+pub(crate) struct FillDelegatesArgs {
+    pub traits: Vec<TraitEntry>,
+    /// Arguments of `#[thin_delegate::fill_delegate]` common to all traits.
+    pub common_args: TokenStream,
+}
+
+/// `impl<R: Renderer> RenderElement<R> { <args> }` in `traits(...)`, where `impl<...>` and
+/// `{ <args> }` are optional.
+#[derive(Debug)]
+pub(crate) struct TraitEntry {
+    pub generics: syn::Generics,
+    pub path: syn::Path,
+    /// Arguments of `#[thin_delegate::fill_delegate]` only for this trait.
+    pub args: TokenStream,
+}
+
+impl FillDelegatesArgs {
+    /// Returns arguments of `#[thin_delegate::fill_delegate]` for the trait `entry`.
+    pub fn args_for(&self, entry: &TraitEntry) -> TokenStream {
+        let mut args = self.common_args.clone();
+        let ends_with_comma = matches!(
+            args.clone().into_iter().last(),
+            Some(proc_macro2::TokenTree::Punct(punct)) if punct.as_char() == ','
+        );
+        if !args.is_empty() && !entry.args.is_empty() && !ends_with_comma {
+            args.extend(quote::quote! { , });
+        }
+        args.extend(entry.args.clone());
+        args
+    }
+}
+
+impl Parse for FillDelegatesArgs {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        if !input.peek(kw::traits) {
+            return Err(input.error("expected `traits(...)` as the first argument"));
+        }
+        input.parse::<kw::traits>()?;
+        let content;
+        syn::parenthesized!(content in input);
+        let traits = content.parse_terminated(TraitEntry::parse, syn::Token![,])?;
+        if traits.is_empty() {
+            return Err(content.error("expected at least one trait"));
+        }
+        if !input.is_empty() {
+            input.parse::<syn::Token![,]>()?;
+        }
+        let common_args = input.parse::<TokenStream>()?;
+
+        // Check them early for better error messages.
+        syn::parse2::<FillDelegateArgs>(common_args.clone())?.validate()?;
+        for entry in &traits {
+            syn::parse2::<FillDelegateArgs>(entry.args.clone())?;
+        }
+
+        Ok(FillDelegatesArgs {
+            traits: traits.into_iter().collect(),
+            common_args,
+        })
+    }
+}
+
+impl Parse for TraitEntry {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let generics = if input.peek(syn::Token![impl]) {
+            input.parse::<syn::Token![impl]>()?;
+            input.parse::<syn::Generics>()?
+        } else {
+            syn::Generics::default()
+        };
+        let path = input.parse::<syn::Path>()?;
+        let args = if input.peek(syn::token::Brace) {
+            let content;
+            syn::braced!(content in input);
+            content.parse::<TokenStream>()?
+        } else {
+            TokenStream::new()
+        };
+
+        Ok(TraitEntry {
+            generics,
+            path,
+            args,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use quote::quote;
+    use syn::parse_quote;
+
+    #[test]
+    fn parsable() {
+        let input = quote! {
+            traits(
+                Element,
+                impl<R: Renderer> RenderElement<R> { skip(draw) },
+            ),
+            scheme = |f| f(&self.0),
+        };
+        let args = syn::parse2::<FillDelegatesArgs>(input).unwrap();
+        assert_eq!(args.traits.len(), 2);
+        assert_eq!(args.traits[0].path, parse_quote! { Element });
+        assert!(args.traits[0].generics.params.is_empty());
+        assert_eq!(args.traits[1].path, parse_quote! { RenderElement<R> });
+        assert_eq!(args.traits[1].generics, parse_quote! { <R: Renderer> });
+        assert_eq!(
+            args.args_for(&args.traits[0]).to_string(),
+            quote! { scheme = |f| f(&self.0), }.to_string()
+        );
+        assert_eq!(
+            args.args_for(&args.traits[1]).to_string(),
+            quote! { scheme = |f| f(&self.0), skip(draw) }.to_string()
+        );
+
+        let input = quote! { traits(Element { skip(draw) }), unit_variants = panic };
+        let args = syn::parse2::<FillDelegatesArgs>(input).unwrap();
+        assert_eq!(
+            args.args_for(&args.traits[0]).to_string(),
+            quote! { unit_variants = panic, skip(draw) }.to_string()
+        );
+
+        assert!(syn::parse2::<FillDelegatesArgs>(quote! {}).is_err());
+        assert!(syn::parse2::<FillDelegatesArgs>(quote! { traits() }).is_err());
+        assert!(syn::parse2::<FillDelegatesArgs>(quote! { scheme = |f| f(&self.0) }).is_err());
+        assert!(syn::parse2::<FillDelegatesArgs>(quote! { traits(Element), hoge = 1 }).is_err());
+        assert!(syn::parse2::<FillDelegatesArgs>(quote! { traits(Element { hoge }) }).is_err());
+    }
+}
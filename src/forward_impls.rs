@@ -0,0 +1,301 @@
+use crate::register_args::PointerKind;
+use proc_macro2::{Span, TokenStream, TokenTree};
+use quote::{format_ident, quote};
+
+impl PointerKind {
+    fn self_ty(self, t: &syn::Ident) -> TokenStream {
+        match self {
+            PointerKind::Ref => quote! { &'__thin_delegate #t },
+            PointerKind::RefMut => quote! { &'__thin_delegate mut #t },
+            PointerKind::Box => quote! { ::std::boxed::Box<#t> },
+            PointerKind::Rc => quote! { ::std::rc::Rc<#t> },
+            PointerKind::Arc => quote! { ::std::sync::Arc<#t> },
+        }
+    }
+
+    fn display(self) -> &'static str {
+        match self {
+            PointerKind::Ref => "&T",
+            PointerKind::RefMut => "&mut T",
+            PointerKind::Box => "Box<T>",
+            PointerKind::Rc => "Rc<T>",
+            PointerKind::Arc => "Arc<T>",
+        }
+    }
+
+    fn supports_mut(self) -> bool {
+        matches!(self, PointerKind::RefMut | PointerKind::Box)
+    }
+}
+
+/// Generates `impl<T: Trait + ?Sized> Trait for &T` and so on for `forward_impls(...)` of
+/// `#[thin_delegate::register]`.
+///
+/// Methods that can't be called through the pointer, e.g. `&mut self` through `&T`, are left to
+/// their default implementations if they are in `keep_default_impl`. It is an error otherwise.
+pub(crate) fn gen_forward_impls(
+    trait_: &syn::ItemTrait,
+    kinds: &[PointerKind],
+    keep_default_impl: &[syn::Ident],
+) -> syn::Result<TokenStream> {
+    let mut errors = Vec::<syn::Error>::new();
+    for ident in keep_default_impl {
+        let has_default_impl = trait_.items.iter().any(|item| match item {
+            syn::TraitItem::Fn(fn_) => fn_.sig.ident == *ident && fn_.default.is_some(),
+            _ => false,
+        });
+        if !has_default_impl {
+            errors.push(syn::Error::new_spanned(
+                ident,
+                format!(
+                    "`{ident}` is not a method of `{}` with default implementation",
+                    trait_.ident
+                ),
+            ));
+        }
+    }
+    let mut impls = vec![];
+    for &kind in kinds {
+        match gen_forward_impl(trait_, kind, keep_default_impl) {
+            Ok(impl_) => impls.push(impl_),
+            Err(e) => errors.push(e),
+        }
+    }
+    if let Some(e) = errors.into_iter().reduce(|mut acc, e| {
+        acc.combine(e);
+        acc
+    }) {
+        return Err(e);
+    }
+
+    Ok(quote! { #(#impls)* })
+}
+
+fn gen_forward_impl(
+    trait_: &syn::ItemTrait,
+    kind: PointerKind,
+    keep_default_impl: &[syn::Ident],
+) -> syn::Result<TokenStream> {
+    let t = syn::Ident::new("__ThinDelegateT", Span::call_site());
+    let trait_ident = &trait_.ident;
+    let (_, trait_ty_generics, _) = trait_.generics.split_for_impl();
+    let trait_path = quote! { #trait_ident #trait_ty_generics };
+    let self_ty = kind.self_ty(&t);
+
+    let mut generics = trait_.generics.clone();
+    if matches!(kind, PointerKind::Ref | PointerKind::RefMut) {
+        generics
+            .params
+            .insert(0, syn::parse_quote! { '__thin_delegate });
+    }
+    generics
+        .params
+        .push(syn::parse_quote! { #t: #trait_path + ?Sized });
+    let where_clause = generics.make_where_clause();
+    if !trait_.supertraits.is_empty() {
+        let supertraits = &trait_.supertraits;
+        where_clause
+            .predicates
+            .push(syn::parse_quote! { #self_ty: #supertraits });
+    }
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+
+    let mut errors = Vec::<syn::Error>::new();
+    let mut items = vec![];
+    for item in &trait_.items {
+        match item {
+            syn::TraitItem::Const(const_) => {
+                let ident = &const_.ident;
+                let ty = &const_.ty;
+                items.push(quote! {
+                    const #ident: #ty = <#t as #trait_path>::#ident;
+                });
+            }
+            syn::TraitItem::Type(type_) => {
+                let ident = &type_.ident;
+                let (_, ty_generics, where_clause) = type_.generics.split_for_impl();
+                let generics = &type_.generics;
+                items.push(quote! {
+                    type #ident #generics = <#t as #trait_path>::#ident #ty_generics #where_clause;
+                });
+            }
+            syn::TraitItem::Fn(fn_) => match gen_forward_fn(&t, &trait_path, fn_, kind) {
+                Some(item) => items.push(item),
+                None if fn_.default.is_some() && keep_default_impl.contains(&fn_.sig.ident) => {}
+                None if fn_.default.is_some() => errors.push(syn::Error::new_spanned(
+                    &fn_.sig.ident,
+                    format!(
+                        "`{}` can't be forwarded through `{}`; add it to `keep_default_impl(...)` to leave it to the default implementation",
+                        fn_.sig.ident,
+                        kind.display()
+                    ),
+                )),
+                None => errors.push(syn::Error::new_spanned(
+                    &fn_.sig.ident,
+                    format!(
+                        "`{}` can't be forwarded through `{}`",
+                        fn_.sig.ident,
+                        kind.display()
+                    ),
+                )),
+            },
+            item => {
+                return Err(syn::Error::new_spanned(
+                    item,
+                    "`forward_impls` supports only functions, constants and types",
+                ))
+            }
+        }
+    }
+    if let Some(e) = errors.into_iter().reduce(|mut acc, e| {
+        acc.combine(e);
+        acc
+    }) {
+        return Err(e);
+    }
+
+    let unsafety = &trait_.unsafety;
+    Ok(quote! {
+        #[automatically_derived]
+        #unsafety impl #impl_generics #trait_path for #self_ty #where_clause {
+            #(#items)*
+        }
+    })
+}
+
+/// Returns `fn method(..) { <T as Trait>::method(..) }`, or `None` if `fn_` can't be called
+/// through `kind`.
+fn gen_forward_fn(
+    t: &syn::Ident,
+    trait_path: &TokenStream,
+    fn_: &syn::TraitItemFn,
+    kind: PointerKind,
+) -> Option<TokenStream> {
+    let mut sig = fn_.sig.clone();
+    // Methods with `where Self: Sized` can't be called with `T: ?Sized`.
+    if let Some(where_clause) = &sig.generics.where_clause {
+        if where_clause.predicates.iter().any(
+            |pred| matches!(pred, syn::WherePredicate::Type(pred) if is_self(&pred.bounded_ty)),
+        ) {
+            return None;
+        }
+    }
+    // `Self` is a different type in the impl.
+    let mentions_self = sig.inputs.iter().any(|arg| match arg {
+        syn::FnArg::Receiver(_) => false,
+        syn::FnArg::Typed(pat_type) => mentions_bare_self(quote! { #pat_type }),
+    }) || {
+        let output = &sig.output;
+        mentions_bare_self(quote! { #output })
+    };
+    if mentions_self {
+        return None;
+    }
+
+    let mut args = vec![];
+    for (i, arg) in sig.inputs.iter_mut().enumerate() {
+        match arg {
+            syn::FnArg::Receiver(receiver) => {
+                receiver.attrs.clear();
+                if receiver.colon_token.is_some() || receiver.reference.is_none() {
+                    return None;
+                }
+                if receiver.mutability.is_some() {
+                    if !kind.supports_mut() {
+                        return None;
+                    }
+                    args.push(quote! { &mut **self });
+                } else {
+                    args.push(quote! { &**self });
+                }
+            }
+            syn::FnArg::Typed(pat_type) => {
+                pat_type.attrs.clear();
+                let ident = match pat_type.pat.as_ref() {
+                    syn::Pat::Ident(pat) => pat.ident.clone(),
+                    _ => format_ident!("arg{}", i),
+                };
+                pat_type.pat = Box::new(syn::parse_quote! { #ident });
+                args.push(quote! { #ident });
+            }
+        }
+    }
+
+    let ident = &sig.ident;
+    let await_ = sig.asyncness.map(|_| quote! { .await });
+    let body = quote! { <#t as #trait_path>::#ident(#(#args),*) #await_ };
+    let body = if sig.unsafety.is_some() {
+        quote! { unsafe { #body } }
+    } else {
+        body
+    };
+    let cfg_attrs = fn_.attrs.iter().filter(|attr| attr.path().is_ident("cfg"));
+    Some(quote! {
+        #(#cfg_attrs)*
+        #[inline]
+        #sig {
+            #body
+        }
+    })
+}
+
+fn is_self(ty: &syn::Type) -> bool {
+    matches!(ty, syn::Type::Path(path) if path.qself.is_none() && path.path.is_ident("Self"))
+}
+
+/// Returns true if `tokens` contains `Self` that is not a prefix of a path, e.g. `Self::Item`.
+fn mentions_bare_self(tokens: TokenStream) -> bool {
+    let tokens = tokens.into_iter().collect::<Vec<_>>();
+    tokens.iter().enumerate().any(|(i, token)| match token {
+        TokenTree::Ident(ident) if ident == "Self" => !matches!(
+            tokens.get(i + 1),
+            Some(TokenTree::Punct(punct)) if punct.as_char() == ':'
+        ),
+        TokenTree::Group(group) => mentions_bare_self(group.stream()),
+        _ => false,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use syn::parse_quote;
+
+    #[test]
+    fn mentions_bare_self() {
+        assert!(super::mentions_bare_self(quote! { -> Self }));
+        assert!(super::mentions_bare_self(quote! { -> Option<Self> }));
+        assert!(!super::mentions_bare_self(quote! { -> Self::Item }));
+        assert!(!super::mentions_bare_self(quote! { -> Option<Self::Item> }));
+        assert!(!super::mentions_bare_self(quote! { -> usize }));
+    }
+
+    #[test]
+    fn unsatisfiable() {
+        let trait_: syn::ItemTrait = parse_quote! {
+            trait Hello {
+                fn hello(&self) -> String;
+                fn set(&mut self, x: usize);
+                fn consume(self) -> usize;
+                fn default_consume(self) -> usize where Self: Sized { 0 }
+            }
+        };
+        assert!(gen_forward_impls(&trait_, &[PointerKind::Ref], &[]).is_err());
+        assert!(gen_forward_impls(&trait_, &[PointerKind::RefMut], &[]).is_err());
+
+        let trait_: syn::ItemTrait = parse_quote! {
+            trait Hello {
+                fn hello(&self) -> String;
+                fn set(&mut self, x: usize);
+                fn consume(self) -> usize where Self: Sized { 0 }
+            }
+        };
+        let keep = [parse_quote! { consume }];
+        assert!(gen_forward_impls(&trait_, &[PointerKind::Rc], &keep).is_err());
+        assert!(
+            gen_forward_impls(&trait_, &[PointerKind::RefMut, PointerKind::Box], &keep).is_ok()
+        );
+        assert!(gen_forward_impls(&trait_, &[PointerKind::RefMut, PointerKind::Box], &[]).is_err());
+        assert!(gen_forward_impls(&trait_, &[PointerKind::Box], &[parse_quote! { set }]).is_err());
+    }
+}
@@ -1,9 +1,11 @@
-use crate::fill_delegate_args::FillDelegateArgs;
+use crate::arm_override::{self, ArmOverride};
+use crate::field_selector::UnitVariantExprs;
+use crate::fill_delegate_args::{FillDelegateArgs, UnitVariants};
 use crate::generic_param_replacer::GenericParamReplacer;
-use crate::{fn_call_replacer, self_replacer};
+use crate::{field_selector, fn_call_replacer, self_replacer, std_defs};
 use proc_macro2::{Span, TokenStream};
-use quote::quote;
-use std::collections::HashSet;
+use quote::{format_ident, quote, ToTokens};
+use std::collections::{HashMap, HashSet};
 use syn::parse_quote;
 use syn::spanned::Spanned;
 
@@ -12,11 +14,17 @@ pub(crate) struct TraitData {
     trait_path: syn::Path,
     generics: syn::Generics,
     sigs: Vec<syn::Signature>,
+    /// `#[cfg(...)]` of the functions, which correspond to `sigs`.
+    cfg_attrs: Vec<Vec<syn::Attribute>>,
+    /// `to_trait` of args.
+    to_trait: Option<syn::Path>,
+    /// Methods of the delegation target to call, which correspond to `sigs`.
+    target_idents: Vec<syn::Ident>,
 }
 
 impl TraitData {
     pub fn new(args: &FillDelegateArgs, trait_: &syn::ItemTrait, trait_path: syn::Path) -> Self {
-        let sigs = trait_
+        let (sigs, cfg_attrs) = trait_
             .items
             .iter()
             .filter_map(|x| {
@@ -28,31 +36,67 @@ impl TraitData {
                 // - that doesn't have default implementation.
                 //   - because it is built on top of necessary functions in many case and we don't
                 //     need to fill them.
+                //
+                // unless it is selected by args. Functions without receiver that have default
+                // implementation, e.g. `Hash::hash_slice()`, are not selected by
+                // `delegate_fn_with_default_impl` because they can't be delegated in general.
 
                 let syn::TraitItem::Fn(fn_) = x else {
                     return None;
                 };
 
-                if !args.delegate_fn_with_default_impl && fn_.default.is_some() {
+                if !args.is_selected(&fn_.sig.ident, fn_.default.is_some()) {
+                    return None;
+                }
+                if fn_.default.is_some()
+                    && fn_.sig.receiver().is_none()
+                    && !args.is_explicitly_selected(&fn_.sig.ident)
+                {
                     return None;
                 }
 
-                Some(fn_.sig.clone())
+                let cfg_attrs = fn_
+                    .attrs
+                    .iter()
+                    .filter(|attr| attr.path().is_ident("cfg"))
+                    .cloned()
+                    .collect::<Vec<_>>();
+                Some((name_args(fn_.sig.clone()), cfg_attrs))
             })
+            .unzip::<_, _, Vec<syn::Signature>, Vec<_>>();
+        let target_idents = sigs
+            .iter()
+            .map(|sig| args.renamed(&sig.ident).clone())
             .collect();
 
         TraitData {
             trait_path,
             generics: trait_.generics.clone(),
             sigs,
+            cfg_attrs,
+            to_trait: args.to_trait.clone(),
+            target_idents,
         }
     }
 
+    /// Returns `#[cfg(...)]` of function `ident` in the trait.
+    fn cfg_attrs_of(&self, ident: &syn::Ident) -> &[syn::Attribute] {
+        self.sigs
+            .iter()
+            .position(|sig| sig.ident == *ident)
+            .map_or(&[], |i| &self.cfg_attrs[i])
+    }
+
     fn fn_ingredients(&self) -> impl Iterator<Item = FnIngredient<'_>> {
-        self.sigs.iter().map(|sig| FnIngredient {
-            trait_path: &self.trait_path,
-            sig,
-        })
+        self.sigs
+            .iter()
+            .zip(&self.target_idents)
+            .map(|(sig, target_ident)| FnIngredient {
+                trait_path: &self.trait_path,
+                sig,
+                target_trait_path: self.to_trait.as_ref().unwrap_or(&self.trait_path),
+                target_ident,
+            })
     }
 
     pub fn validate(&self) -> syn::Result<()> {
@@ -64,14 +108,34 @@ impl TraitData {
     }
 }
 
+/// Names arguments that are not identifiers, e.g. `_`, as `arg{i}` so that they can be passed to
+/// the delegation target.
+fn name_args(mut sig: syn::Signature) -> syn::Signature {
+    for (i, arg) in sig.inputs.iter_mut().enumerate() {
+        if let syn::FnArg::Typed(pat_type) = arg {
+            if !matches!(pat_type.pat.as_ref(), syn::Pat::Ident(_)) {
+                let ident = format_ident!("arg{}", i);
+                pat_type.pat = Box::new(parse_quote! { #ident });
+            }
+        }
+    }
+    sig
+}
+
 struct FnIngredient<'a> {
     trait_path: &'a syn::Path,
     sig: &'a syn::Signature,
+    /// Trait and method of the delegation target to call, which differ from `trait_path` and
+    /// `sig.ident` with `to_trait` and `rename`.
+    target_trait_path: &'a syn::Path,
+    target_ident: &'a syn::Ident,
 }
 
 impl<'a> FnIngredient<'a> {
     pub fn validate(&self) -> syn::Result<()> {
-        self.receiver_prefix()?;
+        // Functions without receiver are also allowed. Whether they can be filled depends on the
+        // struct/enum, see `gen_impl_fn_constructor()`. Arguments that are not identifiers are
+        // named by `name_args()`.
 
         Ok(())
     }
@@ -100,11 +164,15 @@ impl<'a> FnIngredient<'a> {
         Ok(ret)
     }
 
+    pub fn trait_ident(&self) -> &syn::Ident {
+        &self.trait_path.segments.last().unwrap().ident
+    }
+
     pub fn func_path(&self) -> syn::Path {
-        let mut trait_path = self.trait_path.clone();
+        let mut trait_path = self.target_trait_path.clone();
         let generic_args = trait_path.segments.last_mut().unwrap().arguments.clone();
         trait_path.segments.last_mut().unwrap().arguments = syn::PathArguments::None;
-        let method_ident = &self.sig.ident;
+        let method_ident = self.target_ident;
         match generic_args {
             syn::PathArguments::None => parse_quote! { #trait_path::#method_ident },
             syn::PathArguments::AngleBracketed(_) => {
@@ -137,11 +205,61 @@ pub(crate) fn gen_impl(
     args: &FillDelegateArgs,
     trait_: &syn::ItemTrait,
     trait_path: &syn::Path,
+    to_trait: Option<&syn::ItemTrait>,
     structenum: &syn::Item,
-    impl_: syn::ItemImpl,
+    mut impl_: syn::ItemImpl,
 ) -> syn::Result<TokenStream> {
+    validate_selected_methods(args, trait_)?;
+
     let trait_data = TraitData::new(args, trait_, trait_path.clone());
 
+    // The target trait is known if it is the same trait or given by `verify_to_trait = true`.
+    let target_trait = match &args.to_trait {
+        None => Some(trait_),
+        Some(_) => to_trait,
+    };
+    validate_targets(args, &trait_data, target_trait)?;
+
+    for (ident, _) in &args.map_args {
+        if !trait_data
+            .fn_ingredients()
+            .any(|fn_ingredient| fn_ingredient.args().iter().any(|arg| arg.ident == *ident))
+        {
+            return Err(syn::Error::new_spanned(
+                ident,
+                format!("`{ident}` is not an argument of methods filled by delegation"),
+            ));
+        }
+    }
+
+    for ident in args.scheme_methods() {
+        if !trait_data.sigs.iter().any(|sig| sig.ident == *ident) {
+            return Err(syn::Error::new_spanned(
+                ident,
+                format!("`{ident}` is not a method filled by delegation"),
+            ));
+        }
+    }
+
+    if let Some(ident) = &args.constructor_variant {
+        let syn::Item::Enum(enum_) = structenum else {
+            return Err(syn::Error::new_spanned(
+                ident,
+                "`constructor_variant` is only for enum",
+            ));
+        };
+        if !enum_.variants.iter().any(|variant| variant.ident == *ident) {
+            return Err(syn::Error::new_spanned(
+                ident,
+                format!("`{ident}` is not a variant of `{}`", enum_.ident),
+            ));
+        }
+    }
+
+    let arm_overrides = arm_override::take_arm_overrides(&mut impl_)?;
+    validate_arm_overrides(&arm_overrides, &trait_data, structenum, &impl_)?;
+    validate_unit_variant_exprs(trait_, structenum)?;
+
     let generic_param_replacer = GenericParamReplacer::new(
         &trait_data.generics,
         &trait_path.segments.last().unwrap().arguments,
@@ -155,17 +273,95 @@ pub(crate) fn gen_impl(
         func_idents.insert(func.sig.ident.clone());
     }
 
+    let std_defs_module = args
+        .external_trait_def
+        .as_ref()
+        .and_then(std_defs::module_name);
+
     let mut funcs = vec![];
-    for fn_ingredient in trait_data.fn_ingredients() {
-        if func_idents.contains(&fn_ingredient.sig.ident) {
-            continue;
+    if std_defs_module.as_deref() == Some("ops") {
+        if let Some(to_trait) = &args.to_trait {
+            return Err(syn::Error::new_spanned(
+                to_trait,
+                "`to_trait` is not supported for `thin_delegate::std_defs::ops`",
+            ));
+        }
+        if let Some((ident, _)) = args.renames.first() {
+            return Err(syn::Error::new_spanned(
+                ident,
+                "`rename` is not supported for `thin_delegate::std_defs::ops`",
+            ));
+        }
+        if args.map_return.is_some() || args.map_err.is_some() || !args.map_args.is_empty() {
+            return Err(syn::Error::new(
+                Span::call_site(),
+                "`map_return`, `map_err` and `map_arg` are not supported for `thin_delegate::std_defs::ops`",
+            ));
+        }
+        let operand = operand_struct(structenum, &trait_.ident)?;
+        funcs.extend(gen_operator_output(trait_, &impl_));
+        for fn_ingredient in trait_data.fn_ingredients() {
+            if func_idents.contains(&fn_ingredient.sig.ident) {
+                continue;
+            }
+            funcs.push(gen_impl_fn_operator(
+                &generic_param_replacer,
+                &operand,
+                &impl_,
+                fn_ingredient,
+            )?);
+        }
+    } else {
+        if args.infer_bounds {
+            add_inferred_bounds(args, trait_path, structenum, &mut impl_)?;
+        }
+        for fn_ingredient in trait_data.fn_ingredients() {
+            if func_idents.contains(&fn_ingredient.sig.ident) {
+                continue;
+            }
+            if fn_ingredient.sig.receiver().is_none() {
+                funcs.push(gen_impl_fn_constructor(
+                    args,
+                    &generic_param_replacer,
+                    structenum,
+                    &fn_ingredient,
+                )?);
+                continue;
+            }
+            if let Some(overrides) = arm_overrides.get(&fn_ingredient.sig.ident) {
+                let syn::Item::Enum(enum_) = structenum else {
+                    panic!("structenum should be checked in `validate_arm_overrides()`");
+                };
+                funcs.push(gen_impl_fn_enum(
+                    args,
+                    &generic_param_replacer,
+                    enum_,
+                    &fn_ingredient,
+                    overrides,
+                )?);
+                continue;
+            }
+            funcs.push(gen_impl_fn(
+                args,
+                &generic_param_replacer,
+                structenum,
+                fn_ingredient,
+            )?);
+        }
+    }
+
+    // Functions are conditional as in the trait.
+    for func in &mut funcs {
+        if let syn::ImplItem::Fn(func) = func {
+            let cfg_attrs = trait_data.cfg_attrs_of(&func.sig.ident);
+            func.attrs.splice(0..0, cfg_attrs.iter().cloned());
+        }
+    }
+
+    if std_defs_module.as_deref() == Some("iter") {
+        if let Some(item) = gen_iterator_item(trait_, structenum, &impl_) {
+            funcs.insert(0, item);
         }
-        funcs.push(gen_impl_fn(
-            args,
-            &generic_param_replacer,
-            structenum,
-            fn_ingredient,
-        )?);
     }
 
     let mut impl_ = impl_;
@@ -174,6 +370,534 @@ pub(crate) fn gen_impl(
     Ok(quote! { #impl_ })
 }
 
+/// Adds `FieldType: Trait` to the where clause of `impl_` for each delegated field whose type
+/// mentions type parameters of `impl_`, for `infer_bounds = true`.
+///
+/// Nothing is added with `scheme`, as the delegation target is not a field.
+fn add_inferred_bounds(
+    args: &FillDelegateArgs,
+    trait_path: &syn::Path,
+    structenum: &syn::Item,
+    impl_: &mut syn::ItemImpl,
+) -> syn::Result<()> {
+    // Schemes may delegate to something other than the field.
+    if args.scheme.is_some() || !args.method_schemes.is_empty() {
+        return Ok(());
+    }
+    let syn::Type::Path(self_ty) = impl_.self_ty.as_ref() else {
+        return Ok(());
+    };
+    let trait_ident = &trait_path.segments.last().unwrap().ident;
+    let (generics, fields) = match structenum {
+        syn::Item::Struct(struct_) => (
+            &struct_.generics,
+            field_selector::delegate_field(&struct_.fields, trait_ident)?
+                .into_iter()
+                .collect::<Vec<_>>(),
+        ),
+        syn::Item::Enum(enum_) => {
+            let mut fields = vec![];
+            for variant in &enum_.variants {
+                fields.extend(field_selector::delegate_field(
+                    &variant.fields,
+                    trait_ident,
+                )?);
+            }
+            (&enum_.generics, fields)
+        }
+        _ => return Ok(()),
+    };
+
+    // Substitute generic parameters of the struct/enum with arguments in `impl ... for <Self>`.
+    let generic_param_replacer =
+        GenericParamReplacer::new(generics, &self_ty.path.segments.last().unwrap().arguments)?;
+    let type_params = impl_
+        .generics
+        .type_params()
+        .map(|param| param.ident.clone())
+        .collect::<HashSet<_>>();
+    let bound = args.to_trait.as_ref().unwrap_or(trait_path);
+
+    let mut tys = vec![];
+    for (_, field) in fields {
+        let ty = generic_param_replacer.replace_type(field.ty.clone());
+        if !mentions_idents(quote! { #ty }, &type_params) || tys.contains(&ty) {
+            continue;
+        }
+        tys.push(ty);
+    }
+    let where_clause = impl_.generics.make_where_clause();
+    for ty in tys {
+        where_clause.predicates.push(parse_quote! { #ty: #bound });
+    }
+
+    Ok(())
+}
+
+fn mentions_idents(tokens: TokenStream, idents: &HashSet<syn::Ident>) -> bool {
+    tokens.into_iter().any(|token| match token {
+        proc_macro2::TokenTree::Ident(ident) => idents.contains(&ident),
+        proc_macro2::TokenTree::Group(group) => mentions_idents(group.stream(), idents),
+        _ => false,
+    })
+}
+
+fn validate_selected_methods(args: &FillDelegateArgs, trait_: &syn::ItemTrait) -> syn::Result<()> {
+    for ident in args.selected_methods() {
+        let fn_ = trait_.items.iter().find_map(|item| match item {
+            syn::TraitItem::Fn(fn_) if fn_.sig.ident == *ident => Some(fn_),
+            _ => None,
+        });
+        let Some(fn_) = fn_ else {
+            return Err(syn::Error::new_spanned(
+                ident,
+                format!("`{ident}` is not a method of `{}`", trait_.ident),
+            ));
+        };
+        if args.with_default_impl.contains(ident) && fn_.default.is_none() {
+            return Err(syn::Error::new_spanned(
+                ident,
+                format!("`{ident}` doesn't have default implementation"),
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Checks that `rename` designates filled methods and that the methods to call exist in the target
+/// trait with compatible receivers and arities.
+fn validate_targets(
+    args: &FillDelegateArgs,
+    trait_data: &TraitData,
+    target_trait: Option<&syn::ItemTrait>,
+) -> syn::Result<()> {
+    for (ident, _) in &args.renames {
+        if !trait_data.sigs.iter().any(|sig| sig.ident == *ident) {
+            return Err(syn::Error::new_spanned(
+                ident,
+                format!("`{ident}` is not a method filled by delegation"),
+            ));
+        }
+    }
+
+    let Some(target_trait) = target_trait else {
+        return Ok(());
+    };
+    // Generic parameters of the traits are substituted before comparing types.
+    let replacer = GenericParamReplacer::new(
+        &trait_data.generics,
+        &trait_data.trait_path.segments.last().unwrap().arguments,
+    )?;
+    let target_path = args.to_trait.as_ref().unwrap_or(&trait_data.trait_path);
+    let target_replacer = GenericParamReplacer::new(
+        &target_trait.generics,
+        &target_path.segments.last().unwrap().arguments,
+    )?;
+    for fn_ingredient in trait_data.fn_ingredients() {
+        let target_ident = fn_ingredient.target_ident;
+        // Point the rename if given, or `to_trait`.
+        let rename = args
+            .renames
+            .iter()
+            .find(|(from, _)| *from == fn_ingredient.sig.ident);
+        let spanned = match (rename, &args.to_trait) {
+            (Some((_, to)), _) => quote! { #to },
+            (None, Some(to_trait)) => quote! { #to_trait },
+            (None, None) => quote! { #target_ident },
+        };
+        let target_sig = target_trait.items.iter().find_map(|item| match item {
+            syn::TraitItem::Fn(fn_) if fn_.sig.ident == *target_ident => Some(&fn_.sig),
+            _ => None,
+        });
+        let Some(target_sig) = target_sig else {
+            return Err(syn::Error::new_spanned(
+                spanned,
+                format!(
+                    "`{target_ident}` is not a method of `{}`",
+                    target_trait.ident
+                ),
+            ));
+        };
+        let receiver_of = |sig: &syn::Signature| {
+            sig.receiver()
+                .map(|r| (r.reference.is_some(), r.mutability.is_some()))
+        };
+        let mismatch = |detail: String| {
+            syn::Error::new_spanned(
+                &spanned,
+                format!(
+                    "signature of `{}` doesn't match the one of `{}::{target_ident}`{detail}",
+                    fn_ingredient.sig.ident, target_trait.ident
+                ),
+            )
+        };
+        if receiver_of(fn_ingredient.sig) != receiver_of(target_sig)
+            || fn_ingredient.sig.inputs.len() != target_sig.inputs.len()
+        {
+            return Err(mismatch(String::new()));
+        }
+
+        // Types are compared syntactically. Ones converted by `map_arg`, `map_return` and `map_err`
+        // may differ.
+        let typed_args = |sig: &syn::Signature| {
+            sig.inputs
+                .iter()
+                .filter_map(|arg| match arg {
+                    syn::FnArg::Receiver(_) => None,
+                    syn::FnArg::Typed(pat_type) => Some(pat_type.clone()),
+                })
+                .collect::<Vec<_>>()
+        };
+        for (arg, target_arg) in typed_args(fn_ingredient.sig)
+            .into_iter()
+            .zip(typed_args(target_sig))
+        {
+            let syn::Pat::Ident(pat) = arg.pat.as_ref() else {
+                continue;
+            };
+            if args.map_args.iter().any(|(ident, _)| *ident == pat.ident) {
+                continue;
+            }
+            let ty = replacer.replace_type(*arg.ty);
+            let target_ty = target_replacer.replace_type(*target_arg.ty);
+            if ty != target_ty {
+                return Err(mismatch(format!(
+                    "; argument `{}` is `{}` but `{}` is expected",
+                    pat.ident,
+                    ty.to_token_stream(),
+                    target_ty.to_token_stream(),
+                )));
+            }
+        }
+        if args.map_return.is_none() && args.map_err.is_none() {
+            let output_ty = |output: &syn::ReturnType, replacer: &GenericParamReplacer| match output
+            {
+                syn::ReturnType::Default => parse_quote! { () },
+                syn::ReturnType::Type(_, ty) => replacer.replace_type(ty.as_ref().clone()),
+            };
+            let ty = output_ty(&fn_ingredient.sig.output, &replacer);
+            let target_ty = output_ty(&target_sig.output, &target_replacer);
+            if ty != target_ty {
+                return Err(mismatch(format!(
+                    "; return type is `{}` but `{}` is expected",
+                    ty.to_token_stream(),
+                    target_ty.to_token_stream(),
+                )));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Checks that methods in `#[thin_delegate::unit_variant(method = expr, ...)]` are in the trait.
+fn validate_unit_variant_exprs(trait_: &syn::ItemTrait, structenum: &syn::Item) -> syn::Result<()> {
+    let syn::Item::Enum(enum_) = structenum else {
+        return Ok(());
+    };
+    for variant in &enum_.variants {
+        let Some(UnitVariantExprs::PerMethod(exprs)) = field_selector::unit_variant_exprs(variant)?
+        else {
+            continue;
+        };
+        for (ident, _) in &exprs {
+            let in_trait = trait_
+                .items
+                .iter()
+                .any(|item| matches!(item, syn::TraitItem::Fn(fn_) if fn_.sig.ident == *ident));
+            if !in_trait {
+                return Err(syn::Error::new(
+                    Span::call_site(),
+                    format!(
+                        "`#[thin_delegate::unit_variant]` of `{}::{}` designates `{ident}`, which is not a method of trait `{}`",
+                        enum_.ident, variant.ident, trait_.ident
+                    ),
+                ));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn validate_arm_overrides(
+    arm_overrides: &HashMap<syn::Ident, Vec<ArmOverride>>,
+    trait_data: &TraitData,
+    structenum: &syn::Item,
+    impl_: &syn::ItemImpl,
+) -> syn::Result<()> {
+    for (ident, overrides) in arm_overrides {
+        let pat = &overrides[0].pat;
+        if !matches!(structenum, syn::Item::Enum(_)) {
+            return Err(syn::Error::new_spanned(
+                pat,
+                "`#[thin_delegate::arm]` is only for enum",
+            ));
+        }
+        if !trait_data.sigs.iter().any(|sig| sig.ident == *ident) {
+            return Err(syn::Error::new_spanned(
+                pat,
+                format!("`{ident}` is not a method filled by delegation"),
+            ));
+        }
+        // Names of arguments may differ, but types must be the same.
+        let arg_types = |fn_: &syn::ImplItemFn| {
+            fn_.sig
+                .inputs
+                .iter()
+                .map(|arg| match arg {
+                    syn::FnArg::Receiver(receiver) => quote! { #receiver }.to_string(),
+                    syn::FnArg::Typed(pat_type) => pat_type.ty.to_token_stream().to_string(),
+                })
+                .collect::<Vec<_>>()
+        };
+        let first_arg_types = arg_types(&overrides[0].fn_);
+        for override_ in &overrides[1..] {
+            if arg_types(&override_.fn_) != first_arg_types {
+                return Err(syn::Error::new_spanned(
+                    &override_.fn_.sig,
+                    format!("methods `{ident}` with `#[thin_delegate::arm]` must have the same receiver and argument types"),
+                ));
+            }
+        }
+        let is_implemented = impl_
+            .items
+            .iter()
+            .any(|item| matches!(item, syn::ImplItem::Fn(fn_) if fn_.sig.ident == *ident));
+        if is_implemented {
+            return Err(syn::Error::new_spanned(
+                pat,
+                format!("`{ident}` is implemented without `#[thin_delegate::arm]`"),
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Generates `type Item = <Inner as Iterator>::Item;` for `impl Iterator for <single field struct>`
+/// if it is not given.
+fn gen_iterator_item(
+    trait_: &syn::ItemTrait,
+    structenum: &syn::Item,
+    impl_: &syn::ItemImpl,
+) -> Option<syn::ImplItem> {
+    if trait_.ident != "Iterator" {
+        return None;
+    }
+
+    let has_item = impl_
+        .items
+        .iter()
+        .any(|item| matches!(item, syn::ImplItem::Type(type_) if type_.ident == "Item"));
+    if has_item {
+        return None;
+    }
+
+    // For enum, there is no natural choice.
+    let syn::Item::Struct(struct_) = structenum else {
+        return None;
+    };
+    let (_, field) = field_selector::delegate_field(&struct_.fields, &trait_.ident).ok()??;
+    let syn::Type::Path(self_ty) = impl_.self_ty.as_ref() else {
+        return None;
+    };
+
+    // Substitute generic parameters of the struct with arguments in `impl ... for <Self>`.
+    let generic_param_replacer = GenericParamReplacer::new(
+        &struct_.generics,
+        &self_ty.path.segments.last().unwrap().arguments,
+    )
+    .ok()?;
+    let field_ty = generic_param_replacer.replace_type(field.ty.clone());
+
+    Some(parse_quote! {
+        type Item = <#field_ty as ::core::iter::Iterator>::Item;
+    })
+}
+
+/// Operand struct of operators of `thin_delegate::std_defs::ops`.
+struct Operand<'a> {
+    struct_: &'a syn::ItemStruct,
+    /// The field that operators unwrap.
+    field: syn::Member,
+    /// Marker fields like `PhantomData<T>`, which are filled with `Default::default()`.
+    markers: Vec<syn::Member>,
+}
+
+/// Returns the struct and its field that operators of `thin_delegate::std_defs::ops` unwrap.
+///
+/// The field is chosen as the delegation target, and other fields must be marker fields.
+fn operand_struct<'a>(
+    structenum: &'a syn::Item,
+    trait_ident: &syn::Ident,
+) -> syn::Result<Operand<'a>> {
+    let e = syn::Error::new(
+        Span::call_site(),
+        "`thin_delegate::std_defs::ops` supports only struct with exact one field except for marker fields like `PhantomData`.",
+    );
+    let syn::Item::Struct(struct_) = structenum else {
+        return Err(e);
+    };
+    let Some((field, _)) = field_selector::delegate_field(&struct_.fields, trait_ident)? else {
+        return Err(e);
+    };
+    let mut markers = vec![];
+    for (i, f) in struct_.fields.iter().enumerate() {
+        let member = field_selector::member(i, f);
+        if member == field {
+            continue;
+        }
+        if !field_selector::is_marker_type(&f.ty) {
+            return Err(e);
+        }
+        markers.push(member);
+    }
+
+    Ok(Operand {
+        struct_,
+        field,
+        markers,
+    })
+}
+
+/// Returns `Meters` for `impl ... for Meters` and `impl ... for &Meters`.
+fn operand_type_path(impl_: &syn::ItemImpl) -> &syn::TypePath {
+    let self_ty = match impl_.self_ty.as_ref() {
+        syn::Type::Reference(reference) => reference.elem.as_ref(),
+        self_ty => self_ty,
+    };
+    let syn::Type::Path(self_ty) = self_ty else {
+        panic!("self type should be checked in `fill_delegate`");
+    };
+    self_ty
+}
+
+/// Generates `type Output = Meters;` for an operator if it is not given.
+fn gen_operator_output(trait_: &syn::ItemTrait, impl_: &syn::ItemImpl) -> Option<syn::ImplItem> {
+    let in_trait = trait_
+        .items
+        .iter()
+        .any(|item| matches!(item, syn::TraitItem::Type(type_) if type_.ident == "Output"));
+    if !in_trait || has_operator_output(impl_) {
+        return None;
+    }
+
+    let ty = operand_type_path(impl_);
+    Some(parse_quote! {
+        type Output = #ty;
+    })
+}
+
+fn has_operator_output(impl_: &syn::ItemImpl) -> bool {
+    impl_
+        .items
+        .iter()
+        .any(|item| matches!(item, syn::ImplItem::Type(type_) if type_.ident == "Output"))
+}
+
+/// Returns `Some(is_reference)` if `ty` is the struct, i.e. the argument should be unwrapped.
+fn operand_arg_is_reference(
+    ty: &syn::Type,
+    struct_: &syn::ItemStruct,
+    self_is_reference: bool,
+) -> Option<bool> {
+    match ty {
+        syn::Type::Reference(reference) => {
+            operand_arg_is_reference(&reference.elem, struct_, false).map(|_| true)
+        }
+        syn::Type::Path(type_path) if type_path.qself.is_none() => {
+            if type_path.path.is_ident("Self") {
+                Some(self_is_reference)
+            } else if type_path.path.segments.last().unwrap().ident == struct_.ident {
+                Some(false)
+            } else {
+                None
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Generates an operator function of `thin_delegate::std_defs::ops`, e.g.
+///
+/// ```text
+/// fn add(self, rhs: Meters) -> Self::Output {
+///     Meters { 0: Add::add(self.0, rhs.0) }
+/// }
+/// ```
+fn gen_impl_fn_operator(
+    generic_param_replacer: &GenericParamReplacer,
+    operand: &Operand<'_>,
+    impl_: &syn::ItemImpl,
+    fn_ingredient: FnIngredient<'_>,
+) -> syn::Result<syn::ImplItem> {
+    let struct_ = operand.struct_;
+    let field = &operand.field;
+    let sig = generic_param_replacer.replace_signature(fn_ingredient.sig.clone());
+    let sig = self_replacer::make_self_hygienic_in_signature(sig);
+
+    let self_is_reference = matches!(impl_.self_ty.as_ref(), syn::Type::Reference(_));
+    let receiver_prefix = fn_ingredient.receiver_prefix()?;
+    let receiver = if receiver_prefix.is_empty() && self_is_reference {
+        quote! { &self.#field }
+    } else {
+        quote! { #receiver_prefix self.#field }
+    };
+
+    let args = sig
+        .inputs
+        .iter()
+        .filter_map(|arg| match arg {
+            syn::FnArg::Receiver(_) => None,
+            syn::FnArg::Typed(pat_type) => Some(pat_type),
+        })
+        .map(|pat_type| {
+            let syn::Pat::Ident(pat) = pat_type.pat.as_ref() else {
+                panic!("Pat should be an ident in function declaration position.");
+            };
+            let ident = &pat.ident;
+            match operand_arg_is_reference(&pat_type.ty, struct_, self_is_reference) {
+                Some(true) => quote! { &#ident.#field },
+                Some(false) => quote! { #ident.#field },
+                None => quote! { #ident },
+            }
+        });
+
+    // Operators of inner type may have other `Rhs`. Let the compiler infer it.
+    let mut trait_path = fn_ingredient.trait_path.clone();
+    trait_path.segments.last_mut().unwrap().arguments = syn::PathArguments::None;
+    let method_ident = &fn_ingredient.sig.ident;
+    let call = quote! { #trait_path::#method_ident(#receiver #(, #args)*) };
+
+    let body = match &sig.output {
+        syn::ReturnType::Default => call,
+        // The user chose `type Output`. We only know that it should be converted from the output of
+        // inner type.
+        syn::ReturnType::Type(..) if has_operator_output(impl_) => {
+            quote! { ::core::convert::Into::into(#call) }
+        }
+        syn::ReturnType::Type(..) => {
+            let mut path = operand_type_path(impl_).path.clone();
+            path.segments.last_mut().unwrap().arguments = syn::PathArguments::None;
+            let markers = &operand.markers;
+            quote! {
+                #path {
+                    #field: #call,
+                    #(#markers: ::core::default::Default::default(),)*
+                }
+            }
+        }
+    };
+
+    Ok(parse_quote! {
+        #sig {
+            #body
+        }
+    })
+}
+
 fn gen_impl_fn(
     args: &FillDelegateArgs,
     generic_param_replacer: &GenericParamReplacer,
@@ -185,9 +909,11 @@ fn gen_impl_fn(
     }
 
     match item {
-        syn::Item::Enum(enum_) => gen_impl_fn_enum(generic_param_replacer, enum_, &fn_ingredient),
+        syn::Item::Enum(enum_) => {
+            gen_impl_fn_enum(args, generic_param_replacer, enum_, &fn_ingredient, &[])
+        }
         syn::Item::Struct(struct_) => {
-            gen_impl_fn_struct(generic_param_replacer, struct_, &fn_ingredient)
+            gen_impl_fn_struct(args, generic_param_replacer, struct_, &fn_ingredient)
         }
         _ => Err(syn::Error::new(
             item.span(),
@@ -196,24 +922,117 @@ fn gen_impl_fn(
     }
 }
 
+/// Generates a function without receiver that returns `Self`, e.g. `fn new(cfg: Config) -> Self`,
+/// as `Self { field: Trait::new(cfg) }` with the delegated field. Other fields must be marker
+/// fields and are filled with `Default::default()`.
+///
+/// For enum, the variant is given by `constructor_variant` or marked with
+/// `#[thin_delegate::constructor_variant]`.
+///
+/// `map_return` is applied to the value of the field. `map_err` is never applied as `Self` is not
+/// `Result`.
+fn gen_impl_fn_constructor(
+    fill_delegate_args: &FillDelegateArgs,
+    generic_param_replacer: &GenericParamReplacer,
+    structenum: &syn::Item,
+    fn_ingredient: &FnIngredient<'_>,
+) -> syn::Result<syn::ImplItem> {
+    let ident = &fn_ingredient.sig.ident;
+    if !returns_self(fn_ingredient.sig) {
+        return Err(syn::Error::new(
+            Span::call_site(),
+            format!("`{ident}` has no receiver and doesn't return `Self`, so it can't be filled by delegation; implement it manually"),
+        ));
+    }
+
+    let (ctor, fields) = match structenum {
+        syn::Item::Struct(struct_) => (quote! { Self }, &struct_.fields),
+        syn::Item::Enum(enum_) => {
+            let variant = match &fill_delegate_args.constructor_variant {
+                Some(variant_ident) => enum_
+                    .variants
+                    .iter()
+                    .find(|variant| variant.ident == *variant_ident),
+                None => enum_
+                    .variants
+                    .iter()
+                    .find(|variant| field_selector::is_constructor_variant(variant)),
+            };
+            let Some(variant) = variant else {
+                return Err(syn::Error::new(
+                    Span::call_site(),
+                    format!("`{ident}` returns `Self`, but the variant to construct is unknown; give `constructor_variant = <variant>` or mark a variant with `#[thin_delegate::constructor_variant]`"),
+                ));
+            };
+            let variant_ident = &variant.ident;
+            (quote! { Self::#variant_ident }, &variant.fields)
+        }
+        _ => {
+            return Err(syn::Error::new(
+                structenum.span(),
+                "expected `enum ...` or `struct ...`",
+            ))
+        }
+    };
+    let Some((member, _)) = field_selector::delegate_field(fields, fn_ingredient.trait_ident())?
+    else {
+        return Err(syn::Error::new(
+            Span::call_site(),
+            format!("`{ident}` returns `Self`, but there is no field to delegate to"),
+        ));
+    };
+    let mut markers = vec![];
+    for (i, field) in fields.iter().enumerate() {
+        let field_member = field_selector::member(i, field);
+        if field_member == member {
+            continue;
+        }
+        if !field_selector::is_marker_type(&field.ty) {
+            return Err(syn::Error::new_spanned(
+                field,
+                format!("`{ident}` returns `Self`, but this field can't be filled; fields other than the delegated one must be marker fields like `PhantomData`"),
+            ));
+        }
+        markers.push(field_member);
+    }
+
+    let sig = generic_param_replacer.replace_signature(fn_ingredient.sig.clone());
+    let sig = self_replacer::make_self_hygienic_in_signature(sig);
+    let func_path = fn_ingredient.func_path();
+    let args = fn_ingredient
+        .args()
+        .into_iter()
+        .map(|arg| fill_delegate_args.map_arg(&arg.ident));
+    let value = fill_delegate_args.map_return(quote! { #func_path(#(#args),*) }, false);
+    Ok(parse_quote! {
+        #sig {
+            #ctor {
+                #member: #value,
+                #(#markers: ::core::default::Default::default(),)*
+            }
+        }
+    })
+}
+
+/// Returns true if `sig` returns `Self`.
+fn returns_self(sig: &syn::Signature) -> bool {
+    let syn::ReturnType::Type(_, ty) = &sig.output else {
+        return false;
+    };
+    matches!(ty.as_ref(), syn::Type::Path(path) if path.qself.is_none() && path.path.is_ident("Self"))
+}
+
 fn gen_impl_fn_scheme(
     args: &FillDelegateArgs,
     generic_param_replacer: &GenericParamReplacer,
     fn_ingredient: &FnIngredient<'_>,
 ) -> Option<syn::ImplItem> {
-    let (arg, body) = args.scheme_arg_and_body()?;
+    let (arg, body) = args.scheme_arg_and_body(&fn_ingredient.sig.ident)?;
 
     let non_receiver_args = fn_ingredient
         .args()
         .iter()
-        .map(|x| {
-            let path = syn::Path::from(syn::PathSegment::from(x.ident.clone()));
-            syn::Expr::from(syn::ExprPath {
-                attrs: vec![],
-                qself: None,
-                path,
-            })
-        })
+        .map(|x| args.map_arg(&x.ident))
         .collect();
     let body = fn_call_replacer::replace_fn_call_in_expr(
         arg.clone(),
@@ -221,6 +1040,7 @@ fn gen_impl_fn_scheme(
         non_receiver_args,
         body.clone(),
     );
+    let body = args.map_return(quote! { #body }, returns_result(fn_ingredient.sig));
 
     let sig = generic_param_replacer.replace_signature(fn_ingredient.sig.clone());
     let sig = self_replacer::make_self_hygienic_in_signature(sig);
@@ -231,81 +1051,217 @@ fn gen_impl_fn_scheme(
     })
 }
 
+/// Returns true if the return type of `sig` is syntactically `Result<..>`, e.g. `io::Result<()>`.
+fn returns_result(sig: &syn::Signature) -> bool {
+    let syn::ReturnType::Type(_, ty) = &sig.output else {
+        return false;
+    };
+    let syn::Type::Path(type_path) = ty.as_ref() else {
+        return false;
+    };
+    type_path.path.segments.last().unwrap().ident == "Result"
+}
+
 fn gen_impl_fn_enum(
+    fill_delegate_args: &FillDelegateArgs,
     generic_param_replacer: &GenericParamReplacer,
     enum_: &syn::ItemEnum,
     fn_ingredient: &FnIngredient<'_>,
+    overrides: &[ArmOverride],
 ) -> syn::Result<syn::ImplItem> {
     let func_path = fn_ingredient.func_path();
-    let args = fn_ingredient.args();
+    let trait_ident = fn_ingredient.trait_ident();
+
+    // Bodies of overriding arms refer `self` and arguments with the user's spans. So we use the
+    // user's signature in that case.
+    let (sig, arg_idents) = match overrides.first() {
+        Some(override_) => {
+            let sig = override_.fn_.sig.clone();
+            let args = sig
+                .inputs
+                .iter()
+                .filter_map(|arg| match arg {
+                    syn::FnArg::Receiver(_) => None,
+                    syn::FnArg::Typed(pat_type) => match pat_type.pat.as_ref() {
+                        syn::Pat::Ident(ident) => Some(Ok(ident.ident.clone())),
+                        pat => Some(Err(syn::Error::new_spanned(
+                            pat,
+                            "arguments of a method with `#[thin_delegate::arm]` must be identifiers",
+                        ))),
+                    },
+                })
+                .collect::<syn::Result<Vec<_>>>()?;
+            (sig, args)
+        }
+        None => {
+            let sig = generic_param_replacer.replace_signature(fn_ingredient.sig.clone());
+            let sig = self_replacer::make_self_hygienic_in_signature(sig);
+            let args = fn_ingredient
+                .args()
+                .into_iter()
+                .map(|arg| arg.ident.clone())
+                .collect();
+            (sig, args)
+        }
+    };
+    let self_token = sig.receiver().unwrap().self_token;
+    let args = arg_idents
+        .iter()
+        .map(|arg| fill_delegate_args.map_arg(arg))
+        .collect::<Vec<_>>();
+    let call = |receiver: &syn::Ident| {
+        fill_delegate_args.map_return(
+            quote! { #func_path(#receiver #(,#args)*) },
+            returns_result(&sig),
+        )
+    };
+    // Other overrides may name the arguments differently. Bind their names to the ones of `sig`.
+    let override_arms = overrides.iter().map(|override_| {
+        let pat = &override_.pat;
+        let block = &override_.fn_.block;
+        let bindings = override_
+            .fn_
+            .sig
+            .inputs
+            .iter()
+            .filter_map(|arg| match arg {
+                syn::FnArg::Receiver(_) => None,
+                syn::FnArg::Typed(pat_type) => Some(pat_type),
+            })
+            .zip(&arg_idents)
+            .filter_map(|(pat_type, arg)| match pat_type.pat.as_ref() {
+                syn::Pat::Ident(pat_ident) if pat_ident.ident != *arg => Some(quote! {
+                    #[allow(unused_variables)]
+                    let #pat_ident = #arg;
+                }),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        if bindings.is_empty() {
+            quote! { #pat => #block }
+        } else {
+            quote! { #pat => { #(#bindings)* #block } }
+        }
+    });
+
     let match_arms = enum_
         .variants
         .iter()
         .map(|variant| {
             let variant_ident = &variant.ident;
-            match &variant.fields {
-                syn::Fields::Named(fields) => {
-                    if fields.named.len() != 1 {
-                        return Err(syn::Error::new_spanned(
-                            &variant.fields,
-                            "fields of enum variant must be a field",
-                        ));
+            if let Some(body) =
+                gen_unit_variant_body(fill_delegate_args, enum_, variant, fn_ingredient)?
+            {
+                return Ok(quote! {
+                    Self::#variant_ident { .. } => #body
+                });
+            }
+            let Some((member, _)) = field_selector::delegate_field(&variant.fields, trait_ident)?
+            else {
+                let e = match &variant.fields {
+                    syn::Fields::Unit => {
+                        syn::Error::new_spanned(variant, "fields of enum variant must be a field")
                     }
-
-                    let ident = fields.named[0].ident.as_ref().unwrap();
+                    fields => {
+                        syn::Error::new_spanned(fields, "fields of enum variant must be a field")
+                    }
+                };
+                return Err(e);
+            };
+            let rest = (variant.fields.len() > 1).then(|| quote! { , .. });
+            match member {
+                syn::Member::Named(ident) => {
+                    let call = call(&ident);
                     Ok(quote! {
-                        Self::#variant_ident { #ident } => #func_path(#ident #(,#args)*)
+                        Self::#variant_ident { #ident #rest } => #call
                     })
                 }
-                syn::Fields::Unnamed(fields) => {
-                    if fields.unnamed.len() != 1 {
-                        return Err(syn::Error::new_spanned(
-                            &variant.fields,
-                            "fields of enum variant must be a field",
-                        ));
-                    }
-
-                    let ident = syn::Ident::new("x", Span::call_site());
+                syn::Member::Unnamed(_) if variant.fields.len() == 1 => {
+                    let call = call(&syn::Ident::new("x", Span::call_site()));
                     Ok(quote! {
-                        Self::#variant_ident(x) => #func_path(#ident #(,#args)*)
+                        Self::#variant_ident(x) => #call
+                    })
+                }
+                syn::Member::Unnamed(index) => {
+                    let call = call(&syn::Ident::new("x", Span::call_site()));
+                    Ok(quote! {
+                        Self::#variant_ident { #index: x, .. } => #call
                     })
                 }
-                syn::Fields::Unit => Err(syn::Error::new_spanned(
-                    variant,
-                    "fields of enum variant must be a field",
-                )),
             }
         })
         .collect::<syn::Result<Vec<_>>>()?;
 
-    let sig = generic_param_replacer.replace_signature(fn_ingredient.sig.clone());
-    let sig = self_replacer::make_self_hygienic_in_signature(sig);
+    if overrides.is_empty() {
+        return Ok(parse_quote! {
+            #sig {
+                match #self_token {
+                    #(#match_arms,)*
+                }
+            }
+        });
+    }
+
+    // Generated arms for the overridden variants can be unreachable.
     Ok(parse_quote! {
         #sig {
-            match self {
+            #[allow(unreachable_patterns)]
+            match #self_token {
+                #(#override_arms,)*
                 #(#match_arms,)*
             }
         }
     })
 }
 
+/// Generates the body of the arm for a unit (or fieldless) variant, i.e. the expression of
+/// `#[thin_delegate::unit_variant(...)]` for the method or one for `unit_variants = ...`.
+///
+/// Returns `None` if the variant has fields or nothing is designated.
+fn gen_unit_variant_body(
+    args: &FillDelegateArgs,
+    enum_: &syn::ItemEnum,
+    variant: &syn::Variant,
+    fn_ingredient: &FnIngredient<'_>,
+) -> syn::Result<Option<TokenStream>> {
+    if !variant.fields.is_empty() {
+        return Ok(None);
+    }
+
+    if let Some(exprs) = field_selector::unit_variant_exprs(variant)? {
+        if let Some(expr) = exprs.get(&fn_ingredient.sig.ident) {
+            return Ok(Some(quote! { #expr }));
+        }
+    }
+
+    let body = match args.unit_variants {
+        None => return Ok(None),
+        Some(UnitVariants::Unreachable) => quote! { ::core::unreachable!() },
+        Some(UnitVariants::Panic) => {
+            let msg = format!(
+                "`{}::{}` doesn't support `{}`",
+                enum_.ident, variant.ident, fn_ingredient.sig.ident
+            );
+            quote! { ::core::panic!(#msg) }
+        }
+        Some(UnitVariants::Default) => quote! { ::core::default::Default::default() },
+    };
+    Ok(Some(body))
+}
+
 fn gen_impl_fn_struct(
+    fill_delegate_args: &FillDelegateArgs,
     generic_param_replacer: &GenericParamReplacer,
     struct_: &syn::ItemStruct,
     fn_ingredient: &FnIngredient<'_>,
 ) -> syn::Result<syn::ImplItem> {
-    let field_ident = {
-        if struct_.fields.len() != 1 {
-            return Err(syn::Error::new(
-                Span::call_site(),
-                "struct must have exact one field.",
-            ));
-        }
-
-        match &struct_.fields.iter().next().unwrap().ident {
-            Some(ident) => quote! { #ident },
-            None => quote! { 0 },
-        }
+    let Some((field_ident, _)) =
+        field_selector::delegate_field(&struct_.fields, fn_ingredient.trait_ident())?
+    else {
+        return Err(syn::Error::new(
+            Span::call_site(),
+            "struct must have exact one field or a field marked with `#[thin_delegate::delegate]`.",
+        ));
     };
     let receiver_prefix = fn_ingredient.receiver_prefix().unwrap();
     let receiver = quote! { #receiver_prefix self.#field_ident };
@@ -313,10 +1269,17 @@ fn gen_impl_fn_struct(
     let sig = generic_param_replacer.replace_signature(fn_ingredient.sig.clone());
     let sig = self_replacer::make_self_hygienic_in_signature(sig);
     let func_path = fn_ingredient.func_path();
-    let args = fn_ingredient.args();
+    let args = fn_ingredient
+        .args()
+        .into_iter()
+        .map(|arg| fill_delegate_args.map_arg(&arg.ident));
+    let body = fill_delegate_args.map_return(
+        quote! { #func_path(#receiver #(,#args)*) },
+        returns_result(&sig),
+    );
     Ok(parse_quote! {
         #sig {
-            #func_path(#receiver #(,#args)*)
+            #body
         }
     })
 }
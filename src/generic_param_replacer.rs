@@ -2,6 +2,7 @@ use indoc::indoc;
 use itertools::izip;
 use quote::ToTokens;
 use std::collections::HashMap;
+use syn::parse_quote;
 use syn::visit_mut::VisitMut;
 
 /// Replaces generic parameters by generic arguments.
@@ -21,25 +22,27 @@ impl GenericParamReplacer {
             exprs: HashMap::new(),
         };
 
-        let subst = match (orig.params.len(), subst) {
+        let mismatch_error = || {
+            syn::Error::new_spanned(
+                subst,
+                format!(
+                    indoc! {r#"
+                        number of generic parameters must coinside:
+                            in definition of trait         = {orig}
+                            in definition of derive target = {subst}
+                    "#},
+                    orig = orig.to_token_stream(),
+                    subst = subst.to_token_stream(),
+                ),
+            )
+        };
+
+        let mut subst: syn::AngleBracketedGenericArguments = match (orig.params.len(), subst) {
             (0, syn::PathArguments::None) => {
                 return Ok(this);
             }
-            (_, syn::PathArguments::None) => {
-                return Err(syn::Error::new_spanned(
-                    subst,
-                    format!(
-                        indoc! {r#"
-                            number of generic parameters must coinside:
-                                in definition of trait         = {orig}
-                                in definition of derive target = {subst}
-                        "#},
-                        orig = orig.to_token_stream(),
-                        subst = subst.to_token_stream(),
-                    ),
-                ));
-            }
-            (_, syn::PathArguments::AngleBracketed(subst)) => subst,
+            (_, syn::PathArguments::None) => parse_quote! { <> },
+            (_, syn::PathArguments::AngleBracketed(subst)) => subst.clone(),
             _ => {
                 return Err(syn::Error::new_spanned(
                     subst,
@@ -48,19 +51,21 @@ impl GenericParamReplacer {
             }
         };
 
+        // Omitted arguments are the defaults, e.g. `Rhs = Self` in `trait Add<Rhs = Self>`.
+        for o in orig.params.iter().skip(subst.args.len()) {
+            let default = match o {
+                syn::GenericParam::Type(o) => o.default.clone().map(syn::GenericArgument::Type),
+                syn::GenericParam::Const(o) => o.default.clone().map(syn::GenericArgument::Const),
+                syn::GenericParam::Lifetime(_) => None,
+            };
+            let Some(default) = default else {
+                return Err(mismatch_error());
+            };
+            subst.args.push(default);
+        }
+
         if orig.params.len() != subst.args.len() {
-            return Err(syn::Error::new_spanned(
-                subst,
-                format!(
-                    indoc! {r#"
-                        number of generic parameters must coinside:
-                            in definition of trait         = {orig}
-                            in definition of derive target = {subst}
-                    "#},
-                    orig = orig.to_token_stream(),
-                    subst = subst.to_token_stream(),
-                ),
-            ));
+            return Err(mismatch_error());
         }
 
         for (o, s) in izip!(orig.params.iter(), subst.args.iter()) {
@@ -127,6 +132,12 @@ impl GenericParamReplacer {
         visitor.visit_signature_mut(&mut sig);
         sig
     }
+
+    pub fn replace_type(&self, mut ty: syn::Type) -> syn::Type {
+        let mut visitor = Visitor(self);
+        visitor.visit_type_mut(&mut ty);
+        ty
+    }
 }
 
 struct Visitor<'a>(&'a GenericParamReplacer);
@@ -268,4 +279,35 @@ mod tests {
         quote! { Hello<u8, M> },
         quote! { fn hello(&self) -> [u8; M]; },
     }
+
+    test_replace_signature! {
+        type_default,
+        quote! { Add<Rhs = Self> },
+        quote! { fn add(self, rhs: Rhs) -> Self::Output; },
+        quote! { Add },
+        quote! { fn add(self, rhs: Self) -> Self::Output; },
+    }
+
+    test_replace_signature! {
+        type_default_partially_given,
+        quote! { Hello<T, U = T, const N: usize = 4> },
+        quote! { fn hello(&self, u: U) -> [T; N]; },
+        quote! { Hello<u8, u16> },
+        quote! { fn hello(&self, u: u16) -> [u8; 4]; },
+    }
+
+    #[test]
+    fn replace_type() -> Result<(), syn::Error> {
+        // struct Hoge<'a, I>(Peekable<&'a I>);
+        // impl Iterator for Hoge<'static, Chars<'static>> { ... }
+        let orig = syn::parse2::<syn::ItemStruct>(quote! { struct Hoge<'a, I>(Peekable<&'a I>); })
+            .unwrap();
+        let subst = syn::parse2::<syn::PathSegment>(quote! { Hoge<'static, Chars<'static>> })?;
+        let generic_param_replacer = GenericParamReplacer::new(&orig.generics, &subst.arguments)?;
+        let got = generic_param_replacer.replace_type(syn::parse2(quote! { Peekable<&'a I> })?);
+        let expected: syn::Type = syn::parse2(quote! { Peekable<&'static Chars<'static>> })?;
+        assert_eq!(got, expected);
+
+        Ok(())
+    }
 }
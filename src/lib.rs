@@ -5,7 +5,10 @@
 //!
 //! - `#[thin_delegate::register]`: Registers definitions of trait, struct and enum.
 //! - `#[thin_delegate::fill_delegate]`: Derives and fills `impl Trait for StructEnum` by delegation.
+//! - `#[thin_delegate::fill_delegates]`: Fills impls of several traits for a struct/enum at once.
+//! - `thin_delegate::fill_delegate_for!`: Fills impls of a trait for several structs/enums at once.
 //! - `#[thin_delegate::external_trait_def]`: Imports trait definitions in external crates.
+//! - `#[thin_delegate::external_structenum_def]`: Imports struct/enum definitions in external crates.
 //!
 //! There exist similar crates. See [comparison](#comparison) for more details.
 //!
@@ -93,9 +96,30 @@
 //! - `external_trait_def`
 //!   - [Import external trait definition](https://github.com/kenoss/thin_delegate/blob/main/tests/ui/pass_external_trait_def.rs)
 //!   - [Import external trait definition with `use`s](https://github.com/kenoss/thin_delegate/blob/main/tests/ui/pass_external_trait_def_with_uses.rs)
+//!   - [Import a trait definition with the real path](https://github.com/kenoss/thin_delegate/blob/main/tests/ui/pass_external_trait_def_path.rs)
+//!   - [Verify imported trait definition](https://github.com/kenoss/thin_delegate/blob/main/tests/ui/pass_external_trait_def_verify.rs)
+//!   - [Use bundled definitions of `std` traits](https://github.com/kenoss/thin_delegate/blob/main/tests/ui/pass_std_defs.rs)
+//!   - [Delegate `Iterator`](https://github.com/kenoss/thin_delegate/blob/main/tests/ui/pass_std_defs_iter.rs)
+//!   - [Delegate operators of newtype](https://github.com/kenoss/thin_delegate/blob/main/tests/ui/pass_std_defs_ops.rs)
+//! - `external_structenum_def`
+//!   - [Import external struct/enum definition](https://github.com/kenoss/thin_delegate/blob/main/tests/ui/pass_external_structenum_def.rs)
+//! - [Select the field to delegate to by `#[thin_delegate::delegate]`](https://github.com/kenoss/thin_delegate/blob/main/tests/ui/pass_delegate_attr.rs)
+//! - [Ignore `PhantomData` fields](https://github.com/kenoss/thin_delegate/blob/main/tests/ui/pass_ignore_marker_fields.rs)
+//! - [Unit variants](https://github.com/kenoss/thin_delegate/blob/main/tests/ui/pass_unit_variants.rs)
+//! - [Override arms of enum](https://github.com/kenoss/thin_delegate/blob/main/tests/ui/pass_arm_override.rs)
+//! - [Select methods to fill by `skip`, `only` and `with_default_impl`](https://github.com/kenoss/thin_delegate/blob/main/tests/ui/pass_method_selection.rs)
+//! - [Delegate to another trait or methods with other names by `to_trait` and `rename`](https://github.com/kenoss/thin_delegate/blob/main/tests/ui/pass_to_trait.rs)
+//! - [Convert arguments and return values by `map_arg`, `map_err` and `map_return`](https://github.com/kenoss/thin_delegate/blob/main/tests/ui/pass_map_conversions.rs)
+//! - [Fill supertraits by `with_supertraits`](https://github.com/kenoss/thin_delegate/blob/main/tests/ui/pass_with_supertraits.rs)
+//! - [Infer where-bounds of generic impls by `infer_bounds`](https://github.com/kenoss/thin_delegate/blob/main/tests/ui/pass_infer_bounds.rs)
+//! - [Functions without receiver returning `Self`](https://github.com/kenoss/thin_delegate/blob/main/tests/ui/pass_constructors.rs)
+//! - [Fill several traits at once by `fill_delegates`](https://github.com/kenoss/thin_delegate/blob/main/tests/ui/pass_fill_delegates.rs)
+//! - [Fill a trait for several types at once by `fill_delegate_for!`](https://github.com/kenoss/thin_delegate/blob/main/tests/ui/pass_fill_delegate_for.rs)
+//! - [Forward a trait to references and smart pointers by `forward_impls`](https://github.com/kenoss/thin_delegate/blob/main/tests/ui/pass_forward_impls.rs)
 //! - `scheme`
 //!   - [struct](https://github.com/kenoss/thin_delegate/blob/main/tests/ui/pass_scheme.rs)
 //!   - [enum](https://github.com/kenoss/thin_delegate/blob/main/tests/ui/pass_scheme_enum.rs)
+//!   - [per method](https://github.com/kenoss/thin_delegate/blob/main/tests/ui/pass_scheme_per_method.rs)
 //! - Trait admits
 //!   - Generics
 //!     - [complex type parameter](https://github.com/kenoss/thin_delegate/blob/main/tests/ui/pass_generics_specialize_complex.rs)
@@ -105,12 +129,16 @@
 //!     - [`where` and complex method argument](https://github.com/kenoss/thin_delegate/blob/main/tests/ui/pass_substitute_generic_params.rs)
 //!   - [GATs](https://github.com/kenoss/thin_delegate/blob/main/tests/ui/pass_gat.rs)
 //! - [Only fills not implemented methods](https://github.com/kenoss/thin_delegate/blob/main/tests/ui/pass_fill_missing_functions_in_impl.rs)
+//! - [Trait and struct/enum in other modules](https://github.com/kenoss/thin_delegate/blob/main/tests/ui/pass_path_aware_lookup.rs)
+//! - [Type aliases](https://github.com/kenoss/thin_delegate/blob/main/tests/ui/pass_register_type_alias.rs)
 //!
 //! ## How it works
 //!
 //! 1. `#[thin_delegate::register]` defines a declarative macro for each trait/struct/enum definition.
 //! 2. `#[thin_delegate::fill_delegate]` collects related definitions by using those declarative macros and CPS,
 //!    and then calls an attribute macro `#[thin_delegate::__internal__fill_delegate]`.
+//!    The declarative macros are searched next to the trait/struct/enum, i.e. `path::to::<macro>`
+//!    for `impl path::to::Trait for path::to::StructEnum`.
 //! 3. `#[thin_delegate::__internal__fill_delegate]` fills `impl Trait for StructEnum {...}`.
 //!
 //! See [src/decl_macro.rs](https://github.com/kenoss/thin_delegate/blob/main/src/decl_macro.rs) for more details.
@@ -125,6 +153,9 @@
 //! Recommended actions:
 //!
 //! - Make sure that your trait/struct/enum is qualified with `#[thin_delegate::register]` correctly.
+//! - Make sure that the paths of the trait/struct/enum in `impl Trait for StructEnum` point the
+//!   modules that define them. Paths via re-exports `pub use ...` don't work unless the macros are
+//!   also re-exported.
 //! - If you are using an external trait definition, make sure that a path of a module is given by
 //!   an argument `external_trait_def` of `#[thin_delegate::fill_delegate]` and the module is
 //!   qualified with `#[thin_delegate::external_trait_def]`.
@@ -200,41 +231,164 @@
 //!
 //! - Exposes a macro with the same name to the struct/enum.
 
+mod alias_resolver;
+mod arm_override;
 mod attr_remover;
 mod decl_macro;
+mod external_structenum_def_args;
 mod external_trait_def_args;
+mod field_selector;
 mod fill_delegate_args;
+mod fill_delegate_for_input;
+mod fill_delegates_args;
 mod fn_call_replacer;
+mod forward_impls;
 mod gen;
 mod generic_param_replacer;
+mod register_args;
 mod self_replacer;
+mod std_defs;
+mod supertraits;
+mod trait_importer;
+mod trait_verifier;
 
+use crate::external_structenum_def_args::ExternalStructEnumDefArgs;
 use crate::external_trait_def_args::ExternalTraitDefArgs;
 use crate::fill_delegate_args::FillDelegateArgs;
+use crate::fill_delegate_for_input::FillDelegateForInput;
+use crate::fill_delegates_args::FillDelegatesArgs;
 use crate::gen::TraitData;
+use crate::register_args::RegisterArgs;
 use proc_macro2::{Span, TokenStream};
 use quote::{quote, ToTokens};
 use std::ops::Deref;
 use syn::parse_quote;
 use syn::spanned::Spanned;
 
-/// An attribute macro marking a module as "external trait definitions"
+/// An attribute macro marking a module (or a trait) as "external trait definitions"
 ///
 /// See [toplevel documentation](./) for fundamental usage.
 ///
+/// Nested modules are also processed, so you can mirror the module tree of the upstream, e.g.
+/// `external_trait_def = __external_trait_def::input::keyboard` for
+/// `__external_trait_def::input::keyboard::KeyboardTarget`. Nested modules marked with
+/// `#[thin_delegate::external_trait_def]` are processed with their own arguments.
+///
+/// See also [example](https://github.com/kenoss/thin_delegate/blob/main/tests/ui/pass_external_trait_def_nested.rs).
+///
 /// ## Arguments
 ///
 /// ### `with_uses = <bool>`
 ///
 /// If `true`, `#[thin_delegate::fill_delegate]` wraps `impl Trait for StructEnum` within a module
-/// and expands the imports `use ...` in the orginal module to the expanded module. It is convenient
-/// to copy&paste the original definition as is.
+/// and expands the imports `use ...`, type aliases `type ...` and constants `const ...` in the
+/// orginal module to the expanded module. It is convenient to copy&paste the original definition
+/// as is.
 ///
 /// Defaults to `false`.
 ///
 /// See also examples
 /// [[pass](https://github.com/kenoss/thin_delegate/blob/main/tests/ui/pass_external_trait_def_with_uses.rs)]
 /// [[fail](https://github.com/kenoss/thin_delegate/blob/main/tests/ui/fail_user_error_external_trait_def_no_with_uses.rs)].
+///
+/// ### `path = <path>`
+///
+/// `#[thin_delegate::external_trait_def(path = ::std::io::Write)]` can be placed on a copied trait
+/// directly instead of a module. It must be placed before `#[thin_delegate::register]`.
+/// `#[thin_delegate::fill_delegate(external_trait_def = path::to::mod)]` finds it in the module
+/// containing it, and the generated `impl` uses the real path `::std::io::Write`. So you don't need
+/// to import the trait.
+///
+/// See also examples
+/// [[pass](https://github.com/kenoss/thin_delegate/blob/main/tests/ui/pass_external_trait_def_path.rs)]
+/// [[fail](https://github.com/kenoss/thin_delegate/blob/main/tests/ui/fail_user_error_external_trait_def_path.rs)].
+///
+/// ### `verify = <path>`
+///
+/// Designates a path of module that contains the real traits, e.g.
+/// `verify = ::smithay::input::pointer`. For nested modules, their names are appended to the path.
+/// It checks at compile time that the copies in this module match with the real traits:
+///
+/// - Every method/associated type/associated const in the copy exists in the real trait with the
+///   same signature.
+/// - Required methods of the real trait are not missing in the copy.
+///
+/// Errors point the stale lines in the copy. It implements the real traits for a dummy type with
+/// the copied signatures, so names in the copy must be resolvable in this module, e.g. by `use`s.
+///
+/// See also examples
+/// [[pass](https://github.com/kenoss/thin_delegate/blob/main/tests/ui/pass_external_trait_def_verify.rs)]
+/// [[fail](https://github.com/kenoss/thin_delegate/blob/main/tests/ui/fail_user_error_external_trait_def_verify.rs)].
+///
+/// ### `from_file = "<path>"`, `traits(<ident>, ...)`
+///
+/// Imports traits `traits(...)` from a Rust source file instead of copying them by hand. The path
+/// is relative to `CARGO_MANIFEST_DIR`, e.g. a vendored copy of the upstream. The traits are
+/// registered as if they were pasted in the module with `#[thin_delegate::register]`, together with
+/// the `use`s that they need in the modules where they are defined. Types, traits and constants
+/// defined in those modules are also imported, e.g. `use upstream::hello::Event;`. Paths
+/// `crate::...`, `self::...` and `super::...` are rewritten to the paths from the upstream crate.
+/// Attributes other than documentation, `#[cfg(...)]` and `#[cfg_attr(...)]` are removed. Note
+/// that conditions of `cfg` are evaluated in this crate. The module is recompiled if the file is
+/// changed.
+///
+/// ```
+/// // Assume that this module is the crate `upstream`, and `tests/ui/vendor/upstream/src/hello.rs`
+/// // is a copy of `upstream::hello`.
+/// mod upstream {
+///     pub struct Name(pub String);
+///
+///     pub mod hello {
+///         use crate::upstream::Name;
+///
+///         pub trait Hello {
+///             fn hello(&self, name: &Name) -> String;
+///
+///             fn bye(&self) -> String {
+///                 "bye".to_string()
+///             }
+///         }
+///     }
+/// }
+///
+/// #[thin_delegate::external_trait_def(
+///     from_file = "tests/ui/vendor/upstream/src/hello.rs",
+///     traits(Hello),
+///     crate_path = crate::upstream,
+///     with_uses = true,
+/// )]
+/// mod __external_trait_def {}
+///
+/// impl upstream::hello::Hello for String {
+///     fn hello(&self, name: &upstream::Name) -> String {
+///         format!("{self}, {}", name.0)
+///     }
+/// }
+///
+/// #[thin_delegate::register]
+/// struct Hoge(String);
+///
+/// #[thin_delegate::fill_delegate(external_trait_def = __external_trait_def)]
+/// impl upstream::hello::Hello for Hoge {}
+///
+/// fn main() {
+///     use upstream::hello::Hello;
+///
+///     let hoge = Hoge("hello".to_string());
+///     assert_eq!(hoge.hello(&upstream::Name("Alice".to_string())), "hello, Alice");
+/// }
+/// ```
+///
+/// ### `crate_path = <path>`
+///
+/// The path of the upstream crate used to rewrite `crate::...` in the imported file. Defaults to
+/// the directory name before `src`, e.g. `::smithay` for `vendor/smithay/src/input/pointer/mod.rs`.
+///
+/// See also examples
+/// [[pass](https://github.com/kenoss/thin_delegate/blob/main/tests/ui/pass_external_trait_def_from_file.rs)]
+/// [[fail](https://github.com/kenoss/thin_delegate/blob/main/tests/ui/fail_user_error_external_trait_def_from_file.rs)].
+// `fn main()` is needed for `crate::upstream` in the example.
+#[allow(clippy::needless_doctest_main)]
 #[proc_macro_attribute]
 pub fn external_trait_def(
     args: proc_macro::TokenStream,
@@ -250,24 +404,118 @@ pub fn external_trait_def(
 fn external_trait_def_aux(args: TokenStream, item: TokenStream) -> syn::Result<TokenStream> {
     let args = syn::parse2::<ExternalTraitDefArgs>(args)?;
 
-    let e = syn::Error::new(item.span(), "expected `mod ... { ... }`");
+    let e = syn::Error::new(item.span(), "expected `mod ... { ... }` or `trait ...`");
     let item = syn::parse2::<syn::Item>(item).map_err(|_| e.clone())?;
-    let syn::Item::Mod(mut mod_) = item else {
-        return Err(e);
+    let mut mod_ = match item {
+        syn::Item::Trait(trait_) => return external_trait_def_for_trait(&args, trait_),
+        syn::Item::Mod(mod_) => mod_,
+        _ => return Err(e),
     };
+    if args.path.is_some() {
+        return Err(syn::Error::new(
+            Span::call_site(),
+            "`path` is only for `trait ...`",
+        ));
+    }
     let Some(ref mut content) = mod_.content else {
         return Err(e);
     };
 
+    let imported = trait_importer::import(&args)?;
+    if let Some((items, _)) = &imported {
+        content.1.extend(items.iter().cloned());
+    }
+
+    process_external_trait_def_mod(&args, args.verify.clone(), &mut content.1);
+
+    if let Some((_, tracker)) = imported {
+        content.1.push(tracker);
+    }
+
+    Ok(quote! { #mod_ })
+}
+
+/// Processes `#[thin_delegate::external_trait_def(path = ...)] trait ...`.
+fn external_trait_def_for_trait(
+    args: &ExternalTraitDefArgs,
+    mut trait_: syn::ItemTrait,
+) -> syn::Result<TokenStream> {
+    let Some(path) = &args.path else {
+        return Err(syn::Error::new(
+            Span::call_site(),
+            "`path = <path>` is required for `trait ...`",
+        ));
+    };
+    if args.with_uses || args.verify.is_some() {
+        return Err(syn::Error::new(
+            Span::call_site(),
+            "`with_uses` and `verify` are only for `mod ... { ... }`",
+        ));
+    }
+    if args.from_file.is_some() || !args.traits.is_empty() {
+        return Err(syn::Error::new(
+            Span::call_site(),
+            "`from_file` and `traits` are only for `mod ... { ... }`",
+        ));
+    }
+
+    trait_.attrs.push(parse_quote! {
+        #[::thin_delegate::__internal__is_external_marker]
+    });
+    trait_.attrs.push(parse_quote! {
+        #[::thin_delegate::__internal__trait_path(#path)]
+    });
+
+    Ok(quote! { #trait_ })
+}
+
+/// Processes items in a module of `#[thin_delegate::external_trait_def]` and nested modules in it.
+fn process_external_trait_def_mod(
+    args: &ExternalTraitDefArgs,
+    verify: Option<syn::Path>,
+    items: &mut Vec<syn::Item>,
+) {
+    for item in items.iter_mut() {
+        let syn::Item::Mod(mod_) = item else {
+            continue;
+        };
+        // It will be processed by the attribute.
+        let is_marked = mod_.attrs.iter().any(|attr| {
+            attr.path()
+                .segments
+                .last()
+                .is_some_and(|segment| segment.ident == "external_trait_def")
+        });
+        if is_marked {
+            continue;
+        }
+        let Some(ref mut content) = mod_.content else {
+            continue;
+        };
+        let verify = verify.as_ref().map(|verify| {
+            let ident = &mod_.ident;
+            parse_quote! { #verify::#ident }
+        });
+        process_external_trait_def_mod(args, verify, &mut content.1);
+    }
+
+    // Items that signatures of traits may depend on.
     let uses = if args.with_uses {
         let mut uses = vec![];
 
-        for mut item in &mut content.1 {
-            #[allow(clippy::single_match)]
+        for mut item in items.iter_mut() {
             match &mut item {
                 syn::Item::Use(use_) => {
                     use_.attrs.push(parse_quote! { #[allow(unused)] });
-                    uses.push(use_.clone());
+                    uses.push(quote! { #use_ });
+                }
+                syn::Item::Type(type_) => {
+                    type_.attrs.push(parse_quote! { #[allow(unused)] });
+                    uses.push(quote! { #type_ });
+                }
+                syn::Item::Const(const_) => {
+                    const_.attrs.push(parse_quote! { #[allow(unused)] });
+                    uses.push(quote! { #const_ });
                 }
                 _ => {}
             }
@@ -278,7 +526,20 @@ fn external_trait_def_aux(args: TokenStream, item: TokenStream) -> syn::Result<T
         None
     };
 
-    for item in &mut content.1 {
+    if let Some(verify) = &verify {
+        let verifications = items
+            .iter()
+            .filter_map(|item| match item {
+                syn::Item::Trait(trait_) => Some(trait_verifier::gen_verification(trait_, verify)),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        for verification in verifications {
+            items.push(syn::Item::Verbatim(verification));
+        }
+    }
+
+    for item in items.iter_mut() {
         #[allow(clippy::single_match)]
         match item {
             syn::Item::Trait(ref mut trait_) => {
@@ -297,8 +558,104 @@ fn external_trait_def_aux(args: TokenStream, item: TokenStream) -> syn::Result<T
             _ => {}
         }
     }
+}
 
-    Ok(quote! { #mod_ })
+/// An attribute macro marking a module (or a struct/enum) as "external struct/enum definitions"
+///
+/// It is a counterpart of `#[thin_delegate::external_trait_def]` for structs and enums in external
+/// crates whose fields are public, e.g. `either::Either`. Copy the definition and qualify it with
+/// `#[thin_delegate::register]`. The copy only defines a declarative macro, and
+/// `#[thin_delegate::fill_delegate(external_structenum_def = path::to::mod)]` generates
+/// `impl Trait for StructEnum` for the real struct/enum.
+///
+/// Nested modules are also processed.
+///
+/// See also [example](https://github.com/kenoss/thin_delegate/blob/main/tests/ui/pass_external_structenum_def.rs).
+///
+/// ## Arguments
+///
+/// ### `path = <path>`
+///
+/// `#[thin_delegate::external_structenum_def(path = ::either::Either)]` can be placed on a copied
+/// struct/enum directly instead of a module. It must be placed before
+/// `#[thin_delegate::register]`. The generated `impl` uses the real path `::either::Either`. So you
+/// don't need to import the struct/enum.
+#[proc_macro_attribute]
+pub fn external_structenum_def(
+    args: proc_macro::TokenStream,
+    item: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    let item: TokenStream = item.into();
+    match external_structenum_def_aux(args.into(), item.clone()) {
+        Ok(x) => x.into(),
+        Err(e) => TokenStream::from_iter([e.into_compile_error(), item]).into(),
+    }
+}
+
+fn external_structenum_def_aux(args: TokenStream, item: TokenStream) -> syn::Result<TokenStream> {
+    let args = syn::parse2::<ExternalStructEnumDefArgs>(args)?;
+
+    let e = syn::Error::new(
+        item.span(),
+        "expected `mod ... { ... }` or `struct ...` or `enum ...`",
+    );
+    let mut item = syn::parse2::<syn::Item>(item).map_err(|_| e.clone())?;
+    let attrs = match &mut item {
+        syn::Item::Struct(struct_) => &mut struct_.attrs,
+        syn::Item::Enum(enum_) => &mut enum_.attrs,
+        syn::Item::Mod(mod_) => {
+            if args.path.is_some() {
+                return Err(syn::Error::new(
+                    Span::call_site(),
+                    "`path` is only for `struct ...` or `enum ...`",
+                ));
+            }
+            let Some(ref mut content) = mod_.content else {
+                return Err(e);
+            };
+
+            process_external_structenum_def_mod(&mut content.1);
+
+            return Ok(quote! { #item });
+        }
+        _ => return Err(e),
+    };
+    let Some(path) = &args.path else {
+        return Err(syn::Error::new(
+            Span::call_site(),
+            "`path = <path>` is required for `struct ...` or `enum ...`",
+        ));
+    };
+
+    attrs.push(parse_quote! {
+        #[::thin_delegate::__internal__is_external_marker]
+    });
+    attrs.push(parse_quote! {
+        #[::thin_delegate::__internal__structenum_path(#path)]
+    });
+
+    Ok(quote! { #item })
+}
+
+/// Processes items in a module of `#[thin_delegate::external_structenum_def]` and nested modules in
+/// it.
+fn process_external_structenum_def_mod(items: &mut [syn::Item]) {
+    for item in items {
+        let attrs = match item {
+            syn::Item::Struct(struct_) => &mut struct_.attrs,
+            syn::Item::Enum(enum_) => &mut enum_.attrs,
+            syn::Item::Mod(mod_) => {
+                if let Some(ref mut content) = mod_.content {
+                    process_external_structenum_def_mod(&mut content.1);
+                }
+                continue;
+            }
+            _ => continue,
+        };
+        attrs.push(parse_quote! {
+            #[::thin_delegate::__internal__is_external_marker]
+        });
+    }
 }
 
 /// Do not use. This is only used from `thin_delegate` crate internal.
@@ -310,14 +667,163 @@ pub fn __internal__is_external_marker(
     item: proc_macro::TokenStream,
 ) -> proc_macro::TokenStream {
     let item: TokenStream = item.into();
-    syn::Error::new_spanned(item, "#[thin_delegate::register] missing for trait")
+    let msg = match syn::parse2::<syn::Item>(item.clone()) {
+        Ok(syn::Item::Struct(_) | syn::Item::Enum(_)) => {
+            "#[thin_delegate::register] missing for struct/enum"
+        }
+        _ => "#[thin_delegate::register] missing for trait",
+    };
+    syn::Error::new_spanned(item, msg)
         .into_compile_error()
         .into()
 }
 
-/// An attribute macro registering a definition of trait/struct/enum for `#[thin_delegate::fill_delegate]`
+/// An attribute macro registering a definition of trait/struct/enum/type alias for `#[thin_delegate::fill_delegate]`
 ///
 /// See [toplevel documentation](./) for fundamental usage.
+///
+/// ## Type aliases
+///
+/// A type alias of a registered struct/enum can be registered, e.g.
+/// `type GlutinElement = RenderElement<GlesRenderer>;`. `#[thin_delegate::fill_delegate]` treats it
+/// as the struct/enum with generic arguments substituted into the field types.
+/// The path to the struct/enum is resolved where `#[thin_delegate::fill_delegate]` is used. Use
+/// `crate::...` if the alias is in another module.
+///
+/// See also [example](https://github.com/kenoss/thin_delegate/blob/main/tests/ui/pass_register_type_alias.rs).
+///
+/// ## Field attribute `#[thin_delegate::delegate]`
+///
+/// By default, a struct and each variant of an enum must have exact one field. For ones with
+/// multiple fields, mark the field to delegate to with `#[thin_delegate::delegate]`, or
+/// `#[thin_delegate::delegate(TraitA, TraitB)]` to use it only for the traits. It works for all
+/// kinds of receivers unlike `scheme`. `#[thin_delegate::register]` removes the attribute.
+///
+/// Fields of marker types `PhantomData<..>`, `PhantomPinned` and `()` are not counted, e.g.
+/// `struct Handle<T>(Inner, PhantomData<T>)` delegates to `Inner` without the attribute. The only
+/// field is used even if it is a marker field, e.g. `struct Tag<T>(PhantomData<T>)`. They are
+/// detected syntactically, so aliases of them are not recognized.
+///
+/// ```
+/// #[thin_delegate::register]
+/// trait Hello {
+///     fn hello(&self) -> String;
+/// }
+///
+/// impl Hello for String {
+///     fn hello(&self) -> String {
+///         format!("hello, {self}")
+///     }
+/// }
+///
+/// #[thin_delegate::register]
+/// struct Hoge {
+///     id: usize,
+///     #[thin_delegate::delegate]
+///     name: String,
+/// }
+///
+/// #[thin_delegate::fill_delegate]
+/// impl Hello for Hoge {}
+///
+/// let hoge = Hoge { id: 1, name: "Alice".to_string() };
+/// assert_eq!(hoge.id, 1);
+/// assert_eq!(hoge.hello(), "hello, Alice");
+/// ```
+///
+/// See also examples
+/// [[pass](https://github.com/kenoss/thin_delegate/blob/main/tests/ui/pass_delegate_attr.rs)]
+/// [[fail](https://github.com/kenoss/thin_delegate/blob/main/tests/ui/fail_user_error_delegate_attr.rs)].
+///
+/// ## Arguments
+///
+/// ### `export`
+///
+/// By default, the registration is only visible in the current crate. With `export`, it is
+/// exported by `#[macro_export]` with a hidden name and re-exported next to the registered item, so
+/// that crates depending on this crate can use the trait/struct/enum with
+/// `#[thin_delegate::fill_delegate]`. `#[thin_delegate::fill_delegate]` finds registrations by the
+/// paths of the trait and the struct/enum, e.g. `impl core_api::Hello for core_api::Hoge {}`.
+///
+/// The registered item and modules containing it must be `pub`. Paths `crate::...` in the
+/// definition are resolved as paths in the registering crate, but other names are resolved at
+/// `#[thin_delegate::fill_delegate]`. Use `crate::...` for types that appear in signatures.
+///
+/// The hidden name is derived from the crate name and the name and the definition of the item.
+/// Thus exported items with the same name and the same definition in different modules of a crate
+/// conflict.
+///
+/// ```
+/// pub mod core_api {
+///     pub struct Arg;
+///
+///     #[thin_delegate::register(export)]
+///     pub trait Hello {
+///         fn hello(&self, arg: crate::core_api::Arg) -> String;
+///     }
+///
+///     impl Hello for String {
+///         fn hello(&self, _arg: crate::core_api::Arg) -> String {
+///             format!("hello, {self}")
+///         }
+///     }
+/// }
+///
+/// #[thin_delegate::register]
+/// struct Hoge(String);
+///
+/// #[thin_delegate::fill_delegate]
+/// impl core_api::Hello for Hoge {}
+/// #
+/// # fn main() {}
+/// ```
+///
+/// ### `forward_impls`, `keep_default_impl`
+///
+/// `forward_impls(ref, ref_mut, box, rc, arc)` for a trait generates impls of the trait for `&T`,
+/// `&mut T`, `Box<T>`, `Rc<T>` and `Arc<T>` respectively, where `T: Trait + ?Sized`. Any subset of
+/// them can be given.
+///
+/// Methods are forwarded only if the pointer supports the receiver: `&self` is supported by all
+/// and `&mut self` is supported by `ref_mut` and `box`. Other methods, e.g. ones taking `self`,
+/// returning `Self` or having `where Self: Sized`, can't be forwarded, and it is an error unless
+/// they are listed in `keep_default_impl(<method>, ...)` to be left to their default
+/// implementations. `#[cfg]` attributes of methods are kept. If the trait has supertraits, the
+/// impls require the pointer to implement them.
+///
+/// ```
+/// #[thin_delegate::register(forward_impls(ref, ref_mut, box), keep_default_impl(increment))]
+/// trait Counter {
+///     fn get(&self) -> usize;
+///     fn increment(&mut self) {}
+/// }
+///
+/// struct Hoge(usize);
+///
+/// impl Counter for Hoge {
+///     fn get(&self) -> usize {
+///         self.0
+///     }
+///
+///     fn increment(&mut self) {
+///         self.0 += 1;
+///     }
+/// }
+///
+/// fn increment_twice(mut counter: impl Counter) -> usize {
+///     counter.increment();
+///     counter.increment();
+///     counter.get()
+/// }
+///
+/// let mut hoge = Hoge(0);
+/// assert_eq!(increment_twice(&mut hoge), 2);
+/// assert_eq!(increment_twice(Box::new(hoge)), 4);
+/// ```
+///
+/// See also examples
+/// [[pass](https://github.com/kenoss/thin_delegate/blob/main/tests/ui/pass_forward_impls.rs)]
+/// [[fail](https://github.com/kenoss/thin_delegate/blob/main/tests/ui/fail_user_error_forward_impls.rs)].
 #[proc_macro_attribute]
 pub fn register(
     args: proc_macro::TokenStream,
@@ -331,28 +837,47 @@ pub fn register(
 }
 
 fn register_aux(args: TokenStream, item: TokenStream) -> syn::Result<TokenStream> {
-    if !args.is_empty() {
-        return Err(syn::Error::new_spanned(args, "arguments must be empty"));
-    }
+    let args = syn::parse2::<RegisterArgs>(args)?;
 
     let mut item = syn::parse2::<syn::Item>(item.clone()).map_err(|_| {
         syn::Error::new(
             item.span(),
-            "expected `trait ...` or `struct ...` or `enum ...`",
+            "expected `trait ...` or `struct ...` or `enum ...` or `type ...`",
         )
     })?;
-    let is_external = match &item {
-        syn::Item::Trait(trait_) => {
-            #[allow(non_snake_case)]
-            let __internal__is_external_marker: syn::Attribute = parse_quote! {
-                #[::thin_delegate::__internal__is_external_marker]
-            };
-            trait_
-                .attrs
-                .iter()
-                .any(|attr| *attr == __internal__is_external_marker)
+    let is_external = {
+        #[allow(non_snake_case)]
+        let __internal__is_external_marker: syn::Attribute = parse_quote! {
+            #[::thin_delegate::__internal__is_external_marker]
+        };
+        let attrs = match &item {
+            syn::Item::Trait(trait_) => &trait_.attrs[..],
+            syn::Item::Struct(struct_) => &struct_.attrs[..],
+            syn::Item::Enum(enum_) => &enum_.attrs[..],
+            _ => &[],
+        };
+        attrs
+            .iter()
+            .any(|attr| *attr == __internal__is_external_marker)
+    };
+    let forward_impls = match &item {
+        syn::Item::Trait(trait_) if !args.forward_impls.is_empty() => {
+            forward_impls::gen_forward_impls(trait_, &args.forward_impls, &args.keep_default_impl)?
         }
-        _ => false,
+        syn::Item::Trait(_) if !args.keep_default_impl.is_empty() => {
+            return Err(syn::Error::new_spanned(
+                &args.keep_default_impl[0],
+                "`keep_default_impl` requires `forward_impls`",
+            ));
+        }
+        syn::Item::Trait(_) => quote! {},
+        _ if !args.forward_impls.is_empty() => {
+            return Err(syn::Error::new(
+                Span::call_site(),
+                "`forward_impls` is only for traits",
+            ));
+        }
+        _ => quote! {},
     };
     let macro_def = match &item {
         syn::Item::Trait(trait_) => {
@@ -365,23 +890,38 @@ fn register_aux(args: TokenStream, item: TokenStream) -> syn::Result<TokenStream
                 &trait_.ident,
                 trait_.ident.span(),
                 is_external,
+                args.export,
                 trait_,
             )
         }
         syn::Item::Struct(structenum) => decl_macro::define_macro_feed_structenum_def_of(
             &structenum.ident,
             structenum.ident.span(),
+            is_external,
+            args.export,
             &item,
         ),
         syn::Item::Enum(structenum) => decl_macro::define_macro_feed_structenum_def_of(
             &structenum.ident,
             structenum.ident.span(),
+            is_external,
+            args.export,
             &item,
         ),
+        syn::Item::Type(alias) => {
+            let target_path = alias_resolver::target_path(alias)?;
+            decl_macro::define_macro_feed_structenum_def_of_alias(
+                &alias.ident,
+                alias.ident.span(),
+                args.export,
+                alias,
+                &target_path,
+            )
+        }
         _ => {
             return Err(syn::Error::new_spanned(
                 item,
-                "expected `trait ...` or `struct ...` or `enum ...`",
+                "expected `trait ...` or `struct ...` or `enum ...` or `type ...`",
             ));
         }
     };
@@ -390,6 +930,7 @@ fn register_aux(args: TokenStream, item: TokenStream) -> syn::Result<TokenStream
         parse_quote! { ::thin_delegate::__internal__is_external_marker },
         &mut item,
     );
+    field_selector::strip_helper_attrs(&mut item);
 
     if is_external {
         Ok(quote! {
@@ -400,6 +941,8 @@ fn register_aux(args: TokenStream, item: TokenStream) -> syn::Result<TokenStream
             #item
 
             #macro_def
+
+            #forward_impls
         })
     }
 }
@@ -408,15 +951,332 @@ fn register_aux(args: TokenStream, item: TokenStream) -> syn::Result<TokenStream
 ///
 /// See [toplevel documentation](./) for fundamental usage.
 ///
+/// ## Overriding arms of enum
+///
+/// A method qualified with `#[thin_delegate::arm(Pattern)]` gives an arm `Pattern => body` of the
+/// generated `match self`, and other variants are delegated. Multiple methods with the same name
+/// can be given for multiple arms. They must have the same receiver and argument types, but may
+/// name the arguments differently. Arms given by the attribute come first.
+///
+/// ```
+/// #[thin_delegate::register]
+/// trait Hello {
+///     fn hello(&self, prefix: &str) -> String;
+/// }
+///
+/// impl Hello for String {
+///     fn hello(&self, prefix: &str) -> String {
+///         format!("{prefix}, {self}")
+///     }
+/// }
+///
+/// #[thin_delegate::register]
+/// enum Hoge {
+///     A(String),
+///     B(String),
+/// }
+///
+/// #[thin_delegate::fill_delegate]
+/// impl Hello for Hoge {
+///     #[thin_delegate::arm(Self::A(s))]
+///     fn hello(&self, prefix: &str) -> String {
+///         format!("{prefix}, {s}{s}")
+///     }
+/// }
+///
+/// assert_eq!(Hoge::A("a".to_string()).hello("hello"), "hello, aa");
+/// assert_eq!(Hoge::B("b".to_string()).hello("hello"), "hello, b");
+/// ```
+///
+/// See also examples
+/// [[pass](https://github.com/kenoss/thin_delegate/blob/main/tests/ui/pass_arm_override.rs)]
+/// [[fail](https://github.com/kenoss/thin_delegate/blob/main/tests/ui/fail_user_error_arm_override.rs)].
+///
+/// ## Functions without receiver
+///
+/// A function without receiver returning `Self`, e.g. `fn new(cfg: Config) -> Self`, is filled as
+/// `Self { field: Trait::new(cfg) }` with the field to delegate to. Other fields must be marker
+/// fields like `PhantomData`, which are filled with `Default::default()`. For enum, the variant to
+/// construct is given by `constructor_variant = <variant>` or a variant qualified with
+/// `#[thin_delegate::constructor_variant]` in the registered enum. `scheme` is not used for them,
+/// and `map_return` is applied to the value of the field.
+///
+/// Other functions without receiver can't be filled and must be implemented manually.
+///
+/// ```
+/// #[thin_delegate::register]
+/// trait Surface {
+///     fn new(name: &str) -> Self;
+///     fn name(&self) -> String;
+/// }
+///
+/// impl Surface for String {
+///     fn new(name: &str) -> Self {
+///         name.to_string()
+///     }
+///
+///     fn name(&self) -> String {
+///         self.clone()
+///     }
+/// }
+///
+/// #[thin_delegate::register]
+/// struct XdgShellSurface(String);
+///
+/// #[thin_delegate::fill_delegate]
+/// impl Surface for XdgShellSurface {}
+///
+/// #[thin_delegate::register]
+/// enum AnySurface {
+///     #[thin_delegate::constructor_variant]
+///     Xdg(XdgShellSurface),
+///     Layer(String),
+/// }
+///
+/// #[thin_delegate::fill_delegate]
+/// impl Surface for AnySurface {}
+///
+/// assert_eq!(XdgShellSurface::new("Alice").0, "Alice");
+/// assert!(matches!(AnySurface::new("Bob"), AnySurface::Xdg(_)));
+/// ```
+///
+/// See also examples
+/// [[pass](https://github.com/kenoss/thin_delegate/blob/main/tests/ui/pass_constructors.rs)]
+/// [[fail](https://github.com/kenoss/thin_delegate/blob/main/tests/ui/fail_user_error_constructors.rs)].
+///
 /// ## Arguments
 ///
 /// ### `delegate_fn_with_default_impl = <bool>`
 ///
 /// By default (`false`), it doesn't fill trait functions with default implementation.
-/// If `true`, it fills them by delegation.
+/// If `true`, it fills them by delegation. Functions without receiver, e.g. `Hash::hash_slice()`,
+/// are not filled unless they are listed in `only` or `with_default_impl`.
 ///
 /// See also [example](https://github.com/kenoss/thin_delegate/blob/main/tests/ui/pass_delegate_fn_with_default_impl.rs).
 ///
+/// ### `skip(<method>, ...)`, `only(<method>, ...)`, `with_default_impl(<method>, ...)`
+///
+/// Select methods to fill by names.
+///
+/// - `skip`: Doesn't fill the given methods. Useful if they are implemented by macros in the impl
+///   block, which `fill_delegate` can't detect, or left to the default implementations.
+/// - `only`: Fills only the given methods, including ones with default implementation.
+/// - `with_default_impl`: Also fills the given methods with default implementation.
+///
+/// `skip` and `only` can't be used together. Names are checked against the trait definition.
+///
+/// ```
+/// #[thin_delegate::register]
+/// trait Hello {
+///     fn hello(&self) -> String;
+///     fn greet(&self) -> String {
+///         format!("{}!", self.hello())
+///     }
+/// }
+///
+/// impl Hello for String {
+///     fn hello(&self) -> String {
+///         format!("hello, {self}")
+///     }
+/// }
+///
+/// #[thin_delegate::register]
+/// struct Hoge(String);
+///
+/// #[thin_delegate::fill_delegate(with_default_impl(greet))]
+/// impl Hello for Hoge {}
+/// ```
+///
+/// See also examples
+/// [[pass](https://github.com/kenoss/thin_delegate/blob/main/tests/ui/pass_method_selection.rs)]
+/// [[fail](https://github.com/kenoss/thin_delegate/blob/main/tests/ui/fail_user_error_method_selection.rs)].
+///
+/// ### `to_trait = <path>`, `rename(<method> = <method>, ...)`, `verify_to_trait = <bool>`
+///
+/// Fill methods by calling methods of another trait and/or with other names on the delegation
+/// target. Signatures of methods to fill are kept.
+///
+/// ```
+/// mod other {
+///     pub trait RenderLegacy {
+///         fn draw_frame(&self) -> String;
+///     }
+/// }
+///
+/// #[thin_delegate::register]
+/// trait Render {
+///     fn render(&self) -> String;
+/// }
+///
+/// struct Canvas;
+///
+/// impl other::RenderLegacy for Canvas {
+///     fn draw_frame(&self) -> String {
+///         "canvas".to_string()
+///     }
+/// }
+///
+/// #[thin_delegate::register]
+/// struct Window(Canvas);
+///
+/// #[thin_delegate::fill_delegate(to_trait = other::RenderLegacy, rename(render = draw_frame))]
+/// impl Render for Window {}
+/// ```
+///
+/// Methods to call are checked against the trait if it is the same trait. With
+/// `verify_to_trait = true`, they are also checked against `to_trait`, which must be registered
+/// with `#[thin_delegate::register]`. The check compares receivers and the types of arguments and
+/// return values after substituting generic parameters of the traits. Types are compared
+/// syntactically, and ones converted by `map_arg`, `map_return` or `map_err` are not compared.
+///
+/// See also examples
+/// [[pass](https://github.com/kenoss/thin_delegate/blob/main/tests/ui/pass_to_trait.rs)]
+/// [[fail](https://github.com/kenoss/thin_delegate/blob/main/tests/ui/fail_user_error_to_trait.rs)].
+///
+/// ### `map_return = <expr>`, `map_err = <expr>`, `map_arg(<arg> = <expr>, ...)`
+///
+/// Convert values passed to and returned from the delegation target. Each `<expr>` is a function
+/// or a closure, e.g. `Into::into` or `|x| x as u64`.
+///
+/// - `map_arg`: Converts arguments with the given names.
+/// - `map_err`: Converts errors of return values of methods returning `Result<..>`.
+/// - `map_return`: Converts return values of all methods, after `map_err`.
+///
+/// ```
+/// mod inner {
+///     pub struct InnerError;
+///
+///     pub trait Store {
+///         fn get(&self, key: String) -> Result<u32, InnerError>;
+///     }
+/// }
+///
+/// pub struct Error;
+///
+/// impl From<inner::InnerError> for Error {
+///     fn from(_: inner::InnerError) -> Self {
+///         Error
+///     }
+/// }
+///
+/// #[thin_delegate::register]
+/// trait Store {
+///     fn get(&self, key: &str) -> Result<u32, Error>;
+/// }
+///
+/// struct MemStore;
+///
+/// impl inner::Store for MemStore {
+///     fn get(&self, _key: String) -> Result<u32, inner::InnerError> {
+///         Ok(1)
+///     }
+/// }
+///
+/// #[thin_delegate::register]
+/// struct Cache(MemStore);
+///
+/// #[thin_delegate::fill_delegate(
+///     to_trait = inner::Store,
+///     map_arg(key = Into::into),
+///     map_err = From::from,
+/// )]
+/// impl Store for Cache {}
+/// ```
+///
+/// See also examples
+/// [[pass](https://github.com/kenoss/thin_delegate/blob/main/tests/ui/pass_map_conversions.rs)]
+/// [[fail](https://github.com/kenoss/thin_delegate/blob/main/tests/ui/fail_user_error_map_conversions.rs)].
+///
+/// ### `with_supertraits = <bool>`, `with_supertraits(skip(<trait>, ...), include(<trait>, ...))`
+///
+/// If `true`, it also fills impls of supertraits of the trait, as if
+/// `#[thin_delegate::fill_delegate] impl Supertrait for Type {}` were written for each of them.
+/// `external_structenum_def`, `unit_variants` and `infer_bounds` are passed to them.
+///
+/// Supertraits must be registered, except for traits in `std`: ones bundled in
+/// `thin_delegate::std_defs` are filled with them, and the others, including auto traits like
+/// `Send` and derivable traits like `Clone`, are skipped. A supertrait written without path, e.g.
+/// `Display` imported by `use std::fmt::Display;`, is regarded as the one in `std` if
+/// `thin_delegate::std_defs` has a trait with the name. Write a path, e.g. `crate::Display`, for
+/// your own trait with such a name. It is not applied recursively.
+///
+/// `with_supertraits(skip(Named, ...))` is the same as `with_supertraits = true` except that the
+/// given supertraits are not filled, e.g. because they are implemented by hand.
+/// `with_supertraits(include(Debug, ...))` fills the given auto traits and derivable traits too if
+/// they are bundled in `thin_delegate::std_defs`, e.g. `Debug` and `Hash`, or registered. Both can
+/// be given together.
+///
+/// ```
+/// #[thin_delegate::register]
+/// trait IsAlive {
+///     fn alive(&self) -> bool;
+/// }
+///
+/// #[thin_delegate::register]
+/// trait Target: IsAlive + Clone + Send {
+///     fn enter(&self) -> String;
+/// }
+///
+/// #[derive(Clone)]
+/// struct Surface;
+///
+/// impl IsAlive for Surface {
+///     fn alive(&self) -> bool {
+///         true
+///     }
+/// }
+///
+/// impl Target for Surface {
+///     fn enter(&self) -> String {
+///         "surface".to_string()
+///     }
+/// }
+///
+/// #[thin_delegate::register]
+/// #[derive(Clone)]
+/// struct Window(Surface);
+///
+/// #[thin_delegate::fill_delegate(with_supertraits = true)]
+/// impl Target for Window {}
+/// ```
+///
+/// See also examples
+/// [[pass](https://github.com/kenoss/thin_delegate/blob/main/tests/ui/pass_with_supertraits.rs)]
+/// [[fail](https://github.com/kenoss/thin_delegate/blob/main/tests/ui/fail_user_error_with_supertraits.rs)].
+///
+/// ### `infer_bounds = <bool>`
+///
+/// With `infer_bounds = true`, `FieldType: Trait` is added to the where clause of the impl for
+/// each delegated field whose type mentions type parameters of the impl, like derive macros do.
+/// The bound is `FieldType: ToTrait` with `to_trait`. Nothing is added with `scheme = ...` or
+/// `scheme(<method>, ...) = ...`, so bounds must be written by hand with them.
+///
+/// ```
+/// #[thin_delegate::register]
+/// trait Hello {
+///     fn hello(&self) -> String;
+/// }
+///
+/// impl Hello for String {
+///     fn hello(&self) -> String {
+///         format!("hello, {self}")
+///     }
+/// }
+///
+/// #[thin_delegate::register]
+/// struct Wrapper<T>(T);
+///
+/// // Works as `impl<T> Hello for Wrapper<T> where T: Hello {}`.
+/// #[thin_delegate::fill_delegate(infer_bounds = true)]
+/// impl<T> Hello for Wrapper<T> {}
+///
+/// assert_eq!(Wrapper("Alice".to_string()).hello(), "hello, Alice");
+/// ```
+///
+/// See also examples
+/// [[pass](https://github.com/kenoss/thin_delegate/blob/main/tests/ui/pass_infer_bounds.rs)]
+/// [[fail](https://github.com/kenoss/thin_delegate/blob/main/tests/ui/fail_user_error_infer_bounds.rs)].
+///
 /// ### `external_trait_def = <path>`
 ///
 /// Designates a path of module that is qualified by `#[thin_delegate::external_trait_def]` and
@@ -424,10 +1284,80 @@ fn register_aux(args: TokenStream, item: TokenStream) -> syn::Result<TokenStream
 ///
 /// How it works (See also [How it works](./index.html#how-it-works).):
 /// `#[thin_delegate::register]` defines a macro that contains information of a trait.
-/// Normally, `#[thin_delegate::fill_delegate]` searches the macro in current module.
+/// Normally, `#[thin_delegate::fill_delegate]` searches the macro next to the trait, i.e.
+/// `path::to::<macro>` for `impl path::to::Trait for ...`.
 /// The argument `external_trait_def = path::to::mod` modifies it to search
 /// `path::to::mod::<macro>`.
 ///
+/// Definitions of common traits in `std` are bundled as `thin_delegate::std_defs::<module>`, e.g.
+/// `external_trait_def = thin_delegate::std_defs::fmt`. You don't need to copy them.
+/// The following are available:
+///
+/// - `borrow`: `Borrow`, `BorrowMut`
+/// - `convert`: `AsRef`, `AsMut`
+/// - `error`: `Error`
+/// - `fmt`: `Binary`, `Debug`, `Display`, `LowerExp`, `LowerHex`, `Octal`, `Pointer`, `UpperExp`,
+///   `UpperHex`, `Write`
+/// - `hash`: `Hash`, `Hasher`
+/// - `io`: `BufRead`, `Read`, `Seek`, `Write`
+/// - `iter`: `DoubleEndedIterator`, `ExactSizeIterator`, `Iterator`
+/// - `ops`: `Add`, `Sub`, `Mul`, `Div`, `Rem`, `BitAnd`, `BitOr`, `BitXor`, `Shl`, `Shr`, their
+///   `*Assign` variants, `Neg`, `Not`
+///
+/// `iter` is a dedicated mode for the iterator family:
+///
+/// - It fills `type Item = <Inner as Iterator>::Item;` for a struct with a field if you omit it.
+/// - It delegates provided methods that inner iterators often specialize for performance, i.e.
+///   `size_hint`, `count`, `last`, `nth`, `fold`, `next_back`, `nth_back`, `rfold` and `len`.
+///   Other provided methods stay on their defaults regardless of `delegate_fn_with_default_impl`.
+///   (`try_fold` and `try_rfold` can't be delegated because `Try` is unstable.)
+///
+/// `ops` is a dedicated mode for operators of a struct with a field, e.g. `struct Meters(f64)`:
+///
+/// - Arguments of the struct (or `Self`) are unwrapped, e.g. `rhs.0` or `&rhs.0`. Other arguments
+///   are passed as is.
+/// - Output is wrapped with the struct, e.g. `Meters { 0: Add::add(self.0, rhs.0) }`, and
+///   `type Output = Meters;` is filled. If you give `type Output`, output is converted with
+///   `Into::into()` instead.
+/// - Both `impl Add for Meters` and `impl Add for &Meters` are supported. `&<Type>` is rejected for
+///   other traits.
+/// - Marker fields like `PhantomData<U>` are allowed besides the field, e.g.
+///   `struct Meters<U>(f64, PhantomData<U>)`, and filled with `Default::default()`.
+///
+/// See also [example](https://github.com/kenoss/thin_delegate/blob/main/tests/ui/pass_std_defs.rs).
+///
+/// ### `unit_variants = unreachable | panic | default`
+///
+/// By default, every variant of an enum must have a field to delegate to. It designates what the
+/// methods do for unit variants (and variants without fields, e.g. `Empty {}` and `Empty()`):
+///
+/// - `unreachable`: `unreachable!()`
+/// - `panic`: `panic!()` with a message containing the variant and the method
+/// - `default`: returns `Default::default()`
+///
+/// A variant qualified with `#[thin_delegate::unit_variant(expr)]` in the registered enum returns
+/// `expr` in all methods instead, regardless of this argument. As methods usually return different
+/// types, expressions can be given per method, e.g.
+/// `#[thin_delegate::unit_variant(hello = "nobody".into(), len = 0)]`. Methods not listed there
+/// follow this argument. Note that expressions can't refer to `self` and the arguments of methods.
+/// `#[thin_delegate::register]` removes the attribute.
+///
+/// See also [example](https://github.com/kenoss/thin_delegate/blob/main/tests/ui/pass_unit_variants.rs).
+///
+/// ### `constructor_variant = <variant>`
+///
+/// Designates the variant of enum that functions without receiver returning `Self` construct. See
+/// [Functions without receiver](#functions-without-receiver).
+///
+/// ### `external_structenum_def = <path>`
+///
+/// Designates a path of module that is qualified by `#[thin_delegate::external_structenum_def]` and
+/// contains the struct/enum definition qualified with `#[thin_delegate::register]`. It modifies
+/// `#[thin_delegate::fill_delegate]` to search the macro of the struct/enum in `path::to::mod`, as
+/// `external_trait_def` does for the trait.
+///
+/// See also [example](https://github.com/kenoss/thin_delegate/blob/main/tests/ui/pass_external_structenum_def.rs).
+///
 /// ### `scheme = <closure-like>`
 ///
 /// Defines a scheme to generate implementations of methods instead of the default generation
@@ -462,6 +1392,40 @@ fn register_aux(args: TokenStream, item: TokenStream) -> syn::Result<TokenStream
 /// parameter `f` is replaced with a trait method `Hello::hello`, like templates in C++.
 ///
 /// See also [example](https://github.com/kenoss/thin_delegate/blob/main/tests/ui/pass_scheme_enum.rs).
+///
+/// ### `scheme(<method>, ...) = <closure-like>`
+///
+/// Same as `scheme = <closure-like>`, but only applies to the given methods. It can be given
+/// multiple times and together with `scheme = <closure-like>`, which applies to the rest.
+///
+/// ```
+/// #[thin_delegate::register]
+/// trait Hello {
+///     fn hello(&self) -> String;
+///     fn bye(&self) -> String;
+/// }
+///
+/// impl Hello for String {
+///     fn hello(&self) -> String {
+///         format!("hello, {self}")
+///     }
+///
+///     fn bye(&self) -> String {
+///         format!("bye, {self}")
+///     }
+/// }
+///
+/// #[thin_delegate::register]
+/// struct Hoge {
+///     name: String,
+///     nickname: String,
+/// }
+///
+/// #[thin_delegate::fill_delegate(scheme(hello) = |f| f(&self.nickname), scheme = |f| f(&self.name))]
+/// impl Hello for Hoge {}
+/// ```
+///
+/// See also [example](https://github.com/kenoss/thin_delegate/blob/main/tests/ui/pass_scheme_per_method.rs).
 #[proc_macro_attribute]
 pub fn fill_delegate(
     args: proc_macro::TokenStream,
@@ -488,20 +1452,242 @@ fn fill_delegate_aux(args: TokenStream, item: TokenStream) -> syn::Result<TokenS
     let Some((_, trait_path, _)) = &impl_.trait_ else {
         return Err(e);
     };
-    let syn::Type::Path(structenum_path) = impl_.self_ty.deref() else {
-        return Err(e);
+    let structenum_path = structenum_path_of(&impl_, &args)?;
+
+    decl_macro::exec_internal_fill_delegate(
+        trait_path,
+        &[(structenum_path, &impl_, Span::call_site())],
+        &args.external_trait_def,
+        &args.external_structenum_def,
+        args.to_trait_to_verify(),
+        args_as_tokenstream,
+    )
+}
+
+/// Returns the path of the struct/enum of `impl_`.
+fn structenum_path_of<'a>(
+    impl_: &'a syn::ItemImpl,
+    args: &FillDelegateArgs,
+) -> syn::Result<&'a syn::Path> {
+    // `impl Add for &Meters` is also allowed for `thin_delegate::std_defs::ops`.
+    let self_ty = match impl_.self_ty.deref() {
+        syn::Type::Reference(reference) => {
+            let is_ops = args
+                .external_trait_def
+                .as_ref()
+                .and_then(std_defs::module_name)
+                .is_some_and(|module| module == "ops");
+            if !is_ops {
+                return Err(syn::Error::new_spanned(
+                    &impl_.self_ty,
+                    "`impl <Trait> for &<Type>` is supported only for `thin_delegate::std_defs::ops`",
+                ));
+            }
+            reference.elem.deref()
+        }
+        self_ty => self_ty,
     };
+    let syn::Type::Path(structenum_path) = self_ty else {
+        return Err(syn::Error::new_spanned(
+            &impl_.self_ty,
+            "expected a path of struct/enum",
+        ));
+    };
+    Ok(&structenum_path.path)
+}
 
-    let trait_ident = &trait_path.segments.last().unwrap().ident;
-    let structenum_ident = &structenum_path.path.segments.last().unwrap().ident;
+/// Fills impls of several traits for a struct/enum at once.
+///
+/// It is put on a struct/enum registered by `#[thin_delegate::register]`, and works as if
+/// `#[thin_delegate::fill_delegate(<args>)] impl <Trait> for <Type> {}` were written for each trait.
+///
+/// ```
+/// #[thin_delegate::register]
+/// trait Hello {
+///     fn hello(&self) -> String;
+/// }
+///
+/// #[thin_delegate::register]
+/// trait Bye {
+///     fn bye(&self) -> String;
+/// }
+///
+/// impl Hello for String {
+///     fn hello(&self) -> String {
+///         format!("hello, {self}")
+///     }
+/// }
+///
+/// impl Bye for String {
+///     fn bye(&self) -> String {
+///         format!("bye, {self}")
+///     }
+/// }
+///
+/// #[thin_delegate::register]
+/// #[thin_delegate::fill_delegates(traits(Hello, Bye), scheme = |f| f(&self.name))]
+/// struct Hoge {
+///     id: usize,
+///     name: String,
+/// }
+/// ```
+///
+/// ## Arguments
+///
+/// `traits(...)` comes first, and the rest are arguments of `#[thin_delegate::fill_delegate]`
+/// common to all traits.
+///
+/// Each trait in `traits(...)` is `[impl<...>] <Trait> [{ <args> }]`.
+///
+/// - `impl<...>`: Generic parameters of the impl in addition to the ones of the struct/enum, e.g.
+///   `impl<R: Renderer> RenderElement<R>`. Parameters with the same names as the ones of the
+///   struct/enum add bounds to them, e.g. `impl<T: Element> Element` for `struct Wrapper<T>(T)`.
+/// - `{ <args> }`: Arguments of `#[thin_delegate::fill_delegate]` only for the trait. They are put
+///   after the common ones, so they take precedence over the common ones taking a single value.
+///
+/// See also [example](https://github.com/kenoss/thin_delegate/blob/main/tests/ui/pass_fill_delegates.rs).
+#[proc_macro_attribute]
+pub fn fill_delegates(
+    args: proc_macro::TokenStream,
+    item: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    let item: TokenStream = item.into();
+
+    match fill_delegates_aux(args.into(), item.clone()) {
+        Ok(x) => x.into(),
+        Err(e) => TokenStream::from_iter([e.into_compile_error(), item]).into(),
+    }
+}
 
-    Ok(decl_macro::exec_internal_fill_delegate(
-        trait_ident,
-        structenum_ident,
+fn fill_delegates_aux(args: TokenStream, item: TokenStream) -> syn::Result<TokenStream> {
+    let args = syn::parse2::<FillDelegatesArgs>(args)?;
+
+    let e = syn::Error::new(Span::call_site(), "expected struct/enum or type alias");
+    let item = syn::parse2::<syn::Item>(item).map_err(|_| e.clone())?;
+    let (ident, generics) = match &item {
+        syn::Item::Struct(struct_) => (&struct_.ident, &struct_.generics),
+        syn::Item::Enum(enum_) => (&enum_.ident, &enum_.generics),
+        syn::Item::Type(type_) => (&type_.ident, &type_.generics),
+        _ => return Err(e),
+    };
+    let (_, ty_generics, _) = generics.split_for_impl();
+
+    let mut impls = vec![];
+    for entry in &args.traits {
+        // Parameters of the struct/enum in `impl<...>` add bounds to them.
+        let mut impl_generics = generics.clone();
+        for param in &entry.generics.params {
+            let existing = impl_generics
+                .params
+                .iter_mut()
+                .find_map(|x| match (x, param) {
+                    (syn::GenericParam::Type(x), syn::GenericParam::Type(param))
+                        if x.ident == param.ident =>
+                    {
+                        Some(x)
+                    }
+                    _ => None,
+                });
+            match (existing, param) {
+                (Some(existing), syn::GenericParam::Type(param)) => {
+                    existing.bounds.extend(param.bounds.clone());
+                }
+                _ => impl_generics.params.push(param.clone()),
+            }
+        }
+        let (impl_generics, _, where_clause) = impl_generics.split_for_impl();
+        let trait_path = &entry.path;
+        let impl_ = quote! {
+            impl #impl_generics #trait_path for #ident #ty_generics #where_clause {}
+        };
+        impls.push(fill_delegate_aux(args.args_for(entry), impl_)?);
+    }
+
+    Ok(quote! {
+        #item
+
+        #(#impls)*
+    })
+}
+
+/// Fills impls of a trait for several structs/enums at once.
+///
+/// It works as if `#[thin_delegate::fill_delegate(<args>)] impl <Trait> for <Type> {}` were written
+/// for each type, but the definition of the trait is fetched only once.
+///
+/// ```
+/// #[thin_delegate::register]
+/// trait Hello {
+///     fn hello(&self) -> String;
+/// }
+///
+/// impl Hello for String {
+///     fn hello(&self) -> String {
+///         format!("hello, {self}")
+///     }
+/// }
+///
+/// #[thin_delegate::register]
+/// struct Name(String);
+///
+/// #[thin_delegate::register]
+/// struct Title(String);
+///
+/// #[thin_delegate::register]
+/// struct Wrapper<T>(T);
+///
+/// thin_delegate::fill_delegate_for! {
+///     impl Hello for Name, Title, <T: Hello> Wrapper<T>
+/// }
+///
+/// assert_eq!(Wrapper(Name("Alice".to_string())).hello(), "hello, Alice");
+/// ```
+///
+/// Arguments of `#[thin_delegate::fill_delegate]` common to all types can be given by
+/// `#[fill_delegate(<args>)]` before `impl`. Each type can have its own generic parameters
+/// `<...>`, which are added to the ones of `impl<...>`.
+///
+/// See also [example](https://github.com/kenoss/thin_delegate/blob/main/tests/ui/pass_fill_delegate_for.rs).
+#[proc_macro]
+pub fn fill_delegate_for(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    match fill_delegate_for_aux(input.into()) {
+        Ok(x) => x.into(),
+        Err(e) => TokenStream::from_iter([e.into_compile_error()]).into(),
+    }
+}
+
+fn fill_delegate_for_aux(input: TokenStream) -> syn::Result<TokenStream> {
+    let input = syn::parse2::<FillDelegateForInput>(input)?;
+    let args = syn::parse2::<FillDelegateArgs>(input.args.clone())?;
+
+    let impls = input.impls();
+    let targets = impls
+        .iter()
+        .map(|impl_| {
+            let structenum_path = structenum_path_of(impl_, &args)?;
+            Ok((structenum_path, impl_, impl_.self_ty.span()))
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    decl_macro::exec_internal_fill_delegate(
+        &input.trait_path,
+        &targets,
         &args.external_trait_def,
-        args_as_tokenstream,
-        &impl_,
-    ))
+        &args.external_structenum_def,
+        args.to_trait_to_verify(),
+        input.args,
+    )
+}
+
+/// Do not use. This is only used from `thin_delegate` crate internal.
+#[doc(hidden)]
+#[allow(non_snake_case)]
+#[proc_macro]
+pub fn __internal__resolve_alias(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    match decl_macro::exec_internal_resolve_alias(input.into()) {
+        Ok(x) => x.into(),
+        Err(e) => TokenStream::from_iter([e.into_compile_error()]).into(),
+    }
 }
 
 /// Do not use. This is only used from `thin_delegate` crate internal.
@@ -529,19 +1715,79 @@ fn internal_fill_delegate_aux(args: TokenStream, item: TokenStream) -> syn::Resu
         panic!();
     };
     let content = mod_.content.unwrap().1;
-    assert_eq!(content.len(), 3);
+    // The definition of `to_trait` is given iff `verify_to_trait = true`.
+    assert_eq!(
+        content.len(),
+        3 + args.to_trait_to_verify().is_some() as usize
+    );
     let mut it = content.into_iter();
     let syn::Item::Trait(trait_) = it.next().unwrap() else {
         panic!();
     };
     let structenum = it.next().unwrap();
-    let syn::Item::Impl(impl_) = it.next().unwrap() else {
+    let to_trait = args.to_trait_to_verify().map(|_| {
+        let syn::Item::Trait(to_trait) = it.next().unwrap() else {
+            panic!();
+        };
+        to_trait
+    });
+    let syn::Item::Impl(mut impl_) = it.next().unwrap() else {
         panic!();
     };
-    let Some((_, trait_path, _)) = &impl_.trait_ else {
+    let Some((_, trait_path, _)) = &mut impl_.trait_ else {
         panic!()
     };
 
+    // `#[thin_delegate::external_trait_def(path = ...)]` remembers the real path of the trait.
+    let trait_path_path = parse_quote! { ::thin_delegate::__internal__trait_path };
+    let real_trait_path = trait_.attrs.iter().find_map(|attr| match &attr.meta {
+        syn::Meta::List(meta) if meta.path == trait_path_path => {
+            Some(syn::parse2::<syn::Path>(meta.tokens.clone()).unwrap())
+        }
+        _ => None,
+    });
+    if let Some(mut real_trait_path) = real_trait_path {
+        real_trait_path.segments.last_mut().unwrap().arguments =
+            trait_path.segments.last().unwrap().arguments.clone();
+        *trait_path = real_trait_path;
+    }
+    let trait_path = trait_path.clone();
+
+    // Generated impls for supertraits are filled by `#[thin_delegate::fill_delegate]` again. So they
+    // use the self type as the user wrote.
+    let supertrait_impls = if args.with_supertraits {
+        supertraits::gen_supertrait_impls(&args, &trait_, &trait_path, &impl_)?
+    } else {
+        vec![]
+    };
+
+    // `#[thin_delegate::external_structenum_def(path = ...)]` remembers the real path of the
+    // struct/enum.
+    let structenum_attrs = match &structenum {
+        syn::Item::Struct(struct_) => &struct_.attrs[..],
+        syn::Item::Enum(enum_) => &enum_.attrs[..],
+        _ => &[],
+    };
+    let structenum_path_path = parse_quote! { ::thin_delegate::__internal__structenum_path };
+    let real_structenum_path = structenum_attrs.iter().find_map(|attr| match &attr.meta {
+        syn::Meta::List(meta) if meta.path == structenum_path_path => {
+            Some(syn::parse2::<syn::Path>(meta.tokens.clone()).unwrap())
+        }
+        _ => None,
+    });
+    if let Some(mut real_structenum_path) = real_structenum_path {
+        let self_ty = match impl_.self_ty.as_mut() {
+            syn::Type::Reference(reference) => reference.elem.as_mut(),
+            self_ty => self_ty,
+        };
+        let syn::Type::Path(self_ty) = self_ty else {
+            panic!("self type should be checked in `fill_delegate`");
+        };
+        real_structenum_path.segments.last_mut().unwrap().arguments =
+            self_ty.path.segments.last().unwrap().arguments.clone();
+        self_ty.path = real_structenum_path;
+    }
+
     let with_uses_path = parse_quote! { ::thin_delegate::__internal__with_uses };
     let uses = trait_.attrs.iter().find_map(|attr| match &attr.meta {
         syn::Meta::List(meta) if meta.path == with_uses_path => Some(meta.tokens.clone()),
@@ -559,7 +1805,14 @@ fn internal_fill_delegate_aux(args: TokenStream, item: TokenStream) -> syn::Resu
     let mod_name = mod_name.replace(|c: char| !c.is_ascii_alphabetic(), "_");
     let mod_name = syn::Ident::new_raw(&mod_name, Span::call_site());
 
-    let impl_ = gen::gen_impl(&args, &trait_, &trait_path.clone(), &structenum, impl_)?;
+    let impl_ = gen::gen_impl(
+        &args,
+        &trait_,
+        &trait_path,
+        to_trait.as_ref(),
+        &structenum,
+        impl_,
+    )?;
 
     if let Some(uses) = uses {
         Ok(quote! {
@@ -569,11 +1822,15 @@ fn internal_fill_delegate_aux(args: TokenStream, item: TokenStream) -> syn::Resu
                 #uses
 
                 #impl_
+
+                #(#supertrait_impls)*
             }
         })
     } else {
         Ok(quote! {
             #impl_
+
+            #(#supertrait_impls)*
         })
     }
 }
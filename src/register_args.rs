@@ -0,0 +1,197 @@
+use syn::parse::Parse;
+
+mod kw {
+    syn::custom_keyword!(export);
+    syn::custom_keyword!(forward_impls);
+    syn::custom_keyword!(keep_default_impl);
+    syn::custom_keyword!(ref_mut);
+    syn::custom_keyword!(rc);
+    syn::custom_keyword!(arc);
+}
+
+#[derive(Debug, Default, PartialEq, Eq)]
+pub(crate) struct RegisterArgs {
+    pub export: bool,
+    pub forward_impls: Vec<PointerKind>,
+    /// Methods left to their default implementations by `forward_impls` if they can't be forwarded,
+    /// i.e. `keep_default_impl(method1, method2)`.
+    pub keep_default_impl: Vec<syn::Ident>,
+}
+
+/// Pointer kinds of `forward_impls(...)`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum PointerKind {
+    /// `ref`, i.e. `&T`
+    Ref,
+    /// `ref_mut`, i.e. `&mut T`
+    RefMut,
+    /// `box`, i.e. `Box<T>`
+    Box,
+    /// `rc`, i.e. `Rc<T>`
+    Rc,
+    /// `arc`, i.e. `Arc<T>`
+    Arc,
+}
+
+impl Parse for RegisterArgs {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let mut this = RegisterArgs::default();
+
+        let args =
+            syn::punctuated::Punctuated::<ParsableArg, syn::Token![,]>::parse_terminated(input)?;
+        for arg in args {
+            match arg {
+                ParsableArg::Export { .. } => {
+                    this.export = true;
+                }
+                ParsableArg::ForwardImpls { kinds, .. } => {
+                    for (kind, span) in kinds {
+                        if this.forward_impls.contains(&kind) {
+                            return Err(syn::Error::new(
+                                span,
+                                "multiple same pointer kinds are given",
+                            ));
+                        }
+                        this.forward_impls.push(kind);
+                    }
+                }
+                ParsableArg::KeepDefaultImpl { methods, .. } => {
+                    this.keep_default_impl.extend(methods);
+                }
+            }
+        }
+
+        Ok(this)
+    }
+}
+
+#[derive(Debug)]
+enum ParsableArg {
+    Export {
+        #[allow(unused)]
+        export_kw: kw::export,
+    },
+    ForwardImpls {
+        #[allow(unused)]
+        forward_impls_kw: kw::forward_impls,
+        #[allow(unused)]
+        paren_token: syn::token::Paren,
+        kinds: Vec<(PointerKind, proc_macro2::Span)>,
+    },
+    KeepDefaultImpl {
+        #[allow(unused)]
+        keep_default_impl_kw: kw::keep_default_impl,
+        #[allow(unused)]
+        paren_token: syn::token::Paren,
+        methods: syn::punctuated::Punctuated<syn::Ident, syn::Token![,]>,
+    },
+}
+
+impl Parse for ParsableArg {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let lookahead = input.lookahead1();
+        if lookahead.peek(kw::export) {
+            Ok(ParsableArg::Export {
+                export_kw: input.parse()?,
+            })
+        } else if lookahead.peek(kw::forward_impls) {
+            let content;
+            Ok(ParsableArg::ForwardImpls {
+                forward_impls_kw: input.parse()?,
+                paren_token: syn::parenthesized!(content in input),
+                kinds: content
+                    .parse_terminated(parse_pointer_kind, syn::Token![,])?
+                    .into_iter()
+                    .collect(),
+            })
+        } else if lookahead.peek(kw::keep_default_impl) {
+            let content;
+            Ok(ParsableArg::KeepDefaultImpl {
+                keep_default_impl_kw: input.parse()?,
+                paren_token: syn::parenthesized!(content in input),
+                methods: content.parse_terminated(syn::Ident::parse, syn::Token![,])?,
+            })
+        } else {
+            Err(input.error("expected `export`, `forward_impls` or `keep_default_impl`"))
+        }
+    }
+}
+
+fn parse_pointer_kind(
+    input: syn::parse::ParseStream,
+) -> syn::Result<(PointerKind, proc_macro2::Span)> {
+    let span = input.span();
+    let lookahead = input.lookahead1();
+    let kind = if lookahead.peek(syn::Token![ref]) {
+        input.parse::<syn::Token![ref]>()?;
+        PointerKind::Ref
+    } else if lookahead.peek(kw::ref_mut) {
+        input.parse::<kw::ref_mut>()?;
+        PointerKind::RefMut
+    } else if lookahead.peek(syn::Token![box]) {
+        input.parse::<syn::Token![box]>()?;
+        PointerKind::Box
+    } else if lookahead.peek(kw::rc) {
+        input.parse::<kw::rc>()?;
+        PointerKind::Rc
+    } else if lookahead.peek(kw::arc) {
+        input.parse::<kw::arc>()?;
+        PointerKind::Arc
+    } else {
+        return Err(lookahead.error());
+    };
+    Ok((kind, span))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use quote::quote;
+    use syn::parse_quote;
+
+    #[test]
+    fn parsable() {
+        let input = quote! {};
+        let expected = RegisterArgs {
+            export: false,
+            forward_impls: vec![],
+            keep_default_impl: vec![],
+        };
+        assert_eq!(syn::parse2::<RegisterArgs>(input).unwrap(), expected);
+
+        let input = quote! { export };
+        let expected = RegisterArgs {
+            export: true,
+            forward_impls: vec![],
+            keep_default_impl: vec![],
+        };
+        assert_eq!(syn::parse2::<RegisterArgs>(input).unwrap(), expected);
+
+        let input = quote! { forward_impls(ref, ref_mut, box, rc, arc), export };
+        let expected = RegisterArgs {
+            export: true,
+            forward_impls: vec![
+                PointerKind::Ref,
+                PointerKind::RefMut,
+                PointerKind::Box,
+                PointerKind::Rc,
+                PointerKind::Arc,
+            ],
+            keep_default_impl: vec![],
+        };
+        assert_eq!(syn::parse2::<RegisterArgs>(input).unwrap(), expected);
+
+        let input = quote! { forward_impls(rc), keep_default_impl(grow, describe) };
+        let expected = RegisterArgs {
+            export: false,
+            forward_impls: vec![PointerKind::Rc],
+            keep_default_impl: vec![parse_quote! { grow }, parse_quote! { describe }],
+        };
+        assert_eq!(syn::parse2::<RegisterArgs>(input).unwrap(), expected);
+
+        assert!(syn::parse2::<RegisterArgs>(quote! { export = true }).is_err());
+        assert!(syn::parse2::<RegisterArgs>(quote! { hoge }).is_err());
+        assert!(syn::parse2::<RegisterArgs>(quote! { forward_impls(cow) }).is_err());
+        assert!(syn::parse2::<RegisterArgs>(quote! { forward_impls(rc, rc) }).is_err());
+    }
+}
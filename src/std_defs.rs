@@ -0,0 +1,516 @@
+// Definitions of traits in `std` bundled with `thin_delegate`.
+//
+// A proc-macro crate can't export modules. So `thin_delegate::std_defs::<module>` is not a real
+// module, and `#[thin_delegate::fill_delegate(external_trait_def = thin_delegate::std_defs::fmt)]`
+// takes the definitions from here instead of searching registrations.
+//
+// Definitions are copied from `std` (as of `rust-version` in Cargo.toml). Unstable methods are
+// omitted, and paths are qualified so that generated impls don't need any imports.
+//
+// `iter` and `ops` are exceptions. See `iter()` and `ops()`.
+
+use syn::parse_quote;
+
+const MODULES: &[&str] = &[
+    "borrow", "convert", "error", "fmt", "hash", "io", "iter", "ops",
+];
+
+/// Returns the name of the bundled module if `path` is `thin_delegate::std_defs::<module>`.
+pub(crate) fn module_name(path: &syn::Path) -> Option<String> {
+    let segments = path
+        .segments
+        .iter()
+        .map(|segment| match segment.arguments {
+            syn::PathArguments::None => Some(segment.ident.to_string()),
+            _ => None,
+        })
+        .collect::<Option<Vec<_>>>()?;
+    match segments.as_slice() {
+        [krate, std_defs, module] if krate == "thin_delegate" && std_defs == "std_defs" => {
+            Some(module.clone())
+        }
+        _ => None,
+    }
+}
+
+pub(crate) fn trait_def(
+    module_path: &syn::Path,
+    trait_ident: &syn::Ident,
+) -> syn::Result<syn::ItemTrait> {
+    let module = module_name(module_path).unwrap();
+    let Some(defs) = defs(&module) else {
+        return Err(syn::Error::new_spanned(
+            module_path,
+            format!(
+                "unknown module `thin_delegate::std_defs::{module}`; expected one of {}",
+                MODULES.join(", "),
+            ),
+        ));
+    };
+    defs.into_iter()
+        .find(|trait_| trait_.ident == *trait_ident)
+        .ok_or_else(|| {
+            syn::Error::new_spanned(
+                trait_ident,
+                format!("`thin_delegate::std_defs::{module}` doesn't have trait `{trait_ident}`"),
+            )
+        })
+}
+
+/// Returns the bundled module that has the trait `path`, e.g. `io` for `std::io::Write`.
+///
+/// `ops` is excluded as it is only for newtypes.
+pub(crate) fn bundled_module_of(path: &syn::Path) -> Option<&'static str> {
+    let trait_ident = &path.segments.last().unwrap().ident;
+    let parent = path.segments.iter().rev().nth(1).map(|x| &x.ident);
+    let mut modules = bundled_modules_of(trait_ident).into_iter();
+    match parent {
+        Some(parent) => modules.find(|module| parent == module),
+        None => modules.next(),
+    }
+}
+
+/// Returns the bundled modules that have a trait named `trait_ident`, e.g. `fmt` and `io` for
+/// `Write`.
+///
+/// `ops` is excluded as it is only for newtypes.
+pub(crate) fn bundled_modules_of(trait_ident: &syn::Ident) -> Vec<&'static str> {
+    MODULES
+        .iter()
+        .copied()
+        .filter(|module| *module != "ops")
+        .filter(|module| {
+            defs(module)
+                .unwrap()
+                .iter()
+                .any(|trait_| trait_.ident == *trait_ident)
+        })
+        .collect()
+}
+
+fn defs(module: &str) -> Option<Vec<syn::ItemTrait>> {
+    let defs = match module {
+        "borrow" => borrow(),
+        "convert" => convert(),
+        "error" => error(),
+        "fmt" => fmt(),
+        "hash" => hash(),
+        "io" => io(),
+        "iter" => iter(),
+        "ops" => ops(),
+        _ => return None,
+    };
+    Some(defs)
+}
+
+fn borrow() -> Vec<syn::ItemTrait> {
+    vec![
+        parse_quote! {
+            pub trait Borrow<Borrowed: ?Sized> {
+                fn borrow(&self) -> &Borrowed;
+            }
+        },
+        parse_quote! {
+            pub trait BorrowMut<Borrowed: ?Sized>: ::core::borrow::Borrow<Borrowed> {
+                fn borrow_mut(&mut self) -> &mut Borrowed;
+            }
+        },
+    ]
+}
+
+fn convert() -> Vec<syn::ItemTrait> {
+    vec![
+        parse_quote! {
+            pub trait AsRef<T: ?Sized> {
+                fn as_ref(&self) -> &T;
+            }
+        },
+        parse_quote! {
+            pub trait AsMut<T: ?Sized> {
+                fn as_mut(&mut self) -> &mut T;
+            }
+        },
+    ]
+}
+
+fn error() -> Vec<syn::ItemTrait> {
+    vec![parse_quote! {
+        pub trait Error: ::core::fmt::Debug + ::core::fmt::Display {
+            fn source(&self) -> ::core::option::Option<&(dyn ::std::error::Error + 'static)> {
+                None
+            }
+
+            fn description(&self) -> &str {
+                "description() is deprecated; use Display"
+            }
+
+            fn cause(&self) -> ::core::option::Option<&dyn ::std::error::Error> {
+                self.source()
+            }
+        }
+    }]
+}
+
+fn fmt() -> Vec<syn::ItemTrait> {
+    let mut defs: Vec<syn::ItemTrait> = [
+        "Binary", "Debug", "Display", "LowerExp", "LowerHex", "Octal", "Pointer", "UpperExp",
+        "UpperHex",
+    ]
+    .into_iter()
+    .map(|name| {
+        let ident = syn::Ident::new(name, proc_macro2::Span::call_site());
+        parse_quote! {
+            pub trait #ident {
+                fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result;
+            }
+        }
+    })
+    .collect();
+    defs.push(parse_quote! {
+        pub trait Write {
+            fn write_str(&mut self, s: &str) -> ::core::fmt::Result;
+
+            fn write_char(&mut self, c: char) -> ::core::fmt::Result {
+                self.write_str(c.encode_utf8(&mut [0; 4]))
+            }
+
+            fn write_fmt(&mut self, args: ::core::fmt::Arguments<'_>) -> ::core::fmt::Result {
+                ::core::fmt::write(self, args)
+            }
+        }
+    });
+    defs
+}
+
+fn hash() -> Vec<syn::ItemTrait> {
+    let mut hasher: syn::ItemTrait = parse_quote! {
+        pub trait Hasher {
+            fn finish(&self) -> u64;
+
+            fn write(&mut self, bytes: &[u8]);
+        }
+    };
+    for ty in [
+        "u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32", "i64", "i128", "isize",
+    ] {
+        let method = syn::Ident::new(&format!("write_{ty}"), proc_macro2::Span::call_site());
+        let ty = syn::Ident::new(ty, proc_macro2::Span::call_site());
+        hasher.items.push(parse_quote! {
+            fn #method(&mut self, i: #ty) {
+                self.write(&i.to_ne_bytes())
+            }
+        });
+    }
+
+    vec![
+        parse_quote! {
+            pub trait Hash {
+                fn hash<H: ::core::hash::Hasher>(&self, state: &mut H);
+
+                fn hash_slice<H: ::core::hash::Hasher>(data: &[Self], state: &mut H)
+                where
+                    Self: Sized,
+                {
+                    for piece in data {
+                        piece.hash(state)
+                    }
+                }
+            }
+        },
+        hasher,
+    ]
+}
+
+fn io() -> Vec<syn::ItemTrait> {
+    vec![
+        parse_quote! {
+            pub trait Read {
+                fn read(&mut self, buf: &mut [u8]) -> ::std::io::Result<usize>;
+
+                fn read_vectored(
+                    &mut self,
+                    bufs: &mut [::std::io::IoSliceMut<'_>],
+                ) -> ::std::io::Result<usize> {
+                    default_read_vectored(|b| self.read(b), bufs)
+                }
+
+                fn read_to_end(&mut self, buf: &mut ::std::vec::Vec<u8>) -> ::std::io::Result<usize> {
+                    default_read_to_end(self, buf, None)
+                }
+
+                fn read_to_string(
+                    &mut self,
+                    buf: &mut ::std::string::String,
+                ) -> ::std::io::Result<usize> {
+                    default_read_to_string(self, buf, None)
+                }
+
+                fn read_exact(&mut self, buf: &mut [u8]) -> ::std::io::Result<()> {
+                    default_read_exact(self, buf)
+                }
+
+                fn by_ref(&mut self) -> &mut Self
+                where
+                    Self: Sized,
+                {
+                    self
+                }
+
+                fn bytes(self) -> ::std::io::Bytes<Self>
+                where
+                    Self: Sized,
+                {
+                    Bytes { inner: self }
+                }
+
+                fn chain<R: ::std::io::Read>(self, next: R) -> ::std::io::Chain<Self, R>
+                where
+                    Self: Sized,
+                {
+                    Chain { first: self, second: next, done_first: false }
+                }
+
+                fn take(self, limit: u64) -> ::std::io::Take<Self>
+                where
+                    Self: Sized,
+                {
+                    Take { inner: self, limit }
+                }
+            }
+        },
+        parse_quote! {
+            pub trait Write {
+                fn write(&mut self, buf: &[u8]) -> ::std::io::Result<usize>;
+
+                fn write_vectored(&mut self, bufs: &[::std::io::IoSlice<'_>]) -> ::std::io::Result<usize> {
+                    default_write_vectored(|b| self.write(b), bufs)
+                }
+
+                fn flush(&mut self) -> ::std::io::Result<()>;
+
+                fn write_all(&mut self, mut buf: &[u8]) -> ::std::io::Result<()> {
+                    while !buf.is_empty() {
+                        match self.write(buf) {
+                            Ok(0) => {
+                                return Err(::std::io::Error::from(::std::io::ErrorKind::WriteZero));
+                            }
+                            Ok(n) => buf = &buf[n..],
+                            Err(ref e) if e.kind() == ::std::io::ErrorKind::Interrupted => {}
+                            Err(e) => return Err(e),
+                        }
+                    }
+                    Ok(())
+                }
+
+                fn write_fmt(&mut self, fmt: ::core::fmt::Arguments<'_>) -> ::std::io::Result<()> {
+                    default_write_fmt(self, fmt)
+                }
+
+                fn by_ref(&mut self) -> &mut Self
+                where
+                    Self: Sized,
+                {
+                    self
+                }
+            }
+        },
+        parse_quote! {
+            pub trait BufRead: ::std::io::Read {
+                fn fill_buf(&mut self) -> ::std::io::Result<&[u8]>;
+
+                fn consume(&mut self, amt: usize);
+
+                fn read_until(
+                    &mut self,
+                    byte: u8,
+                    buf: &mut ::std::vec::Vec<u8>,
+                ) -> ::std::io::Result<usize> {
+                    read_until(self, byte, buf)
+                }
+
+                fn read_line(&mut self, buf: &mut ::std::string::String) -> ::std::io::Result<usize> {
+                    unsafe { append_to_string(buf, |b| read_until(self, b'\n', b)) }
+                }
+
+                fn split(self, byte: u8) -> ::std::io::Split<Self>
+                where
+                    Self: Sized,
+                {
+                    Split { buf: self, delim: byte }
+                }
+
+                fn lines(self) -> ::std::io::Lines<Self>
+                where
+                    Self: Sized,
+                {
+                    Lines { buf: self }
+                }
+            }
+        },
+        parse_quote! {
+            pub trait Seek {
+                fn seek(&mut self, pos: ::std::io::SeekFrom) -> ::std::io::Result<u64>;
+
+                fn rewind(&mut self) -> ::std::io::Result<()> {
+                    self.seek(::std::io::SeekFrom::Start(0))?;
+                    Ok(())
+                }
+
+                fn stream_position(&mut self) -> ::std::io::Result<u64> {
+                    self.seek(::std::io::SeekFrom::Current(0))
+                }
+
+                fn seek_relative(&mut self, offset: i64) -> ::std::io::Result<()> {
+                    self.seek(::std::io::SeekFrom::Current(offset))?;
+                    Ok(())
+                }
+            }
+        },
+    ]
+}
+
+// Iterator traits have many provided methods and most of them are adapters that should stay on
+// their defaults. So we don't copy them as they are. Provided methods that inner iterators often
+// specialize for performance are declared without default implementation so that they are filled
+// by delegation, and the other provided methods are omitted.
+//
+// `try_fold()` and `try_rfold()` are not here because they are bounded by `Try`, which is unstable.
+// Note that `fold()` is also worth to be delegated as many adapters are specialized via it.
+fn iter() -> Vec<syn::ItemTrait> {
+    vec![
+        parse_quote! {
+            pub trait Iterator {
+                type Item;
+
+                fn next(&mut self) -> ::core::option::Option<Self::Item>;
+
+                fn size_hint(&self) -> (usize, ::core::option::Option<usize>);
+
+                fn count(self) -> usize
+                where
+                    Self: Sized;
+
+                fn last(self) -> ::core::option::Option<Self::Item>
+                where
+                    Self: Sized;
+
+                fn nth(&mut self, n: usize) -> ::core::option::Option<Self::Item>;
+
+                fn fold<B, F>(self, init: B, f: F) -> B
+                where
+                    Self: Sized,
+                    F: FnMut(B, Self::Item) -> B;
+            }
+        },
+        parse_quote! {
+            pub trait DoubleEndedIterator: ::core::iter::Iterator {
+                fn next_back(&mut self) -> ::core::option::Option<Self::Item>;
+
+                fn nth_back(&mut self, n: usize) -> ::core::option::Option<Self::Item>;
+
+                fn rfold<B, F>(self, init: B, f: F) -> B
+                where
+                    Self: Sized,
+                    F: FnMut(B, Self::Item) -> B;
+            }
+        },
+        parse_quote! {
+            pub trait ExactSizeIterator: ::core::iter::Iterator {
+                fn len(&self) -> usize;
+            }
+        },
+    ]
+}
+
+// Operators are not delegated as they are. `rhs` of the struct is unwrapped, and output is wrapped
+// with the struct. See `gen::gen_impl_fn_operator()`.
+fn ops() -> Vec<syn::ItemTrait> {
+    let binary = [
+        ("Add", "add"),
+        ("Sub", "sub"),
+        ("Mul", "mul"),
+        ("Div", "div"),
+        ("Rem", "rem"),
+        ("BitAnd", "bitand"),
+        ("BitOr", "bitor"),
+        ("BitXor", "bitxor"),
+        ("Shl", "shl"),
+        ("Shr", "shr"),
+    ];
+    let unary = [("Neg", "neg"), ("Not", "not")];
+
+    let mut defs = vec![];
+    for (trait_name, method_name) in binary {
+        let trait_ident = syn::Ident::new(trait_name, proc_macro2::Span::call_site());
+        let method_ident = syn::Ident::new(method_name, proc_macro2::Span::call_site());
+        defs.push(parse_quote! {
+            pub trait #trait_ident<Rhs = Self> {
+                type Output;
+
+                fn #method_ident(self, rhs: Rhs) -> Self::Output;
+            }
+        });
+
+        let trait_ident = syn::Ident::new(
+            &format!("{trait_name}Assign"),
+            proc_macro2::Span::call_site(),
+        );
+        let method_ident = syn::Ident::new(
+            &format!("{method_name}_assign"),
+            proc_macro2::Span::call_site(),
+        );
+        defs.push(parse_quote! {
+            pub trait #trait_ident<Rhs = Self> {
+                fn #method_ident(&mut self, rhs: Rhs);
+            }
+        });
+    }
+    for (trait_name, method_name) in unary {
+        let trait_ident = syn::Ident::new(trait_name, proc_macro2::Span::call_site());
+        let method_ident = syn::Ident::new(method_name, proc_macro2::Span::call_site());
+        defs.push(parse_quote! {
+            pub trait #trait_ident {
+                type Output;
+
+                fn #method_ident(self) -> Self::Output;
+            }
+        });
+    }
+    defs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fill_delegate_args::FillDelegateArgs;
+    use crate::gen::TraitData;
+
+    #[test]
+    fn module_name() {
+        assert_eq!(
+            super::module_name(&parse_quote! { thin_delegate::std_defs::fmt }),
+            Some("fmt".to_string())
+        );
+        assert_eq!(
+            super::module_name(&parse_quote! { ::thin_delegate::std_defs::io }),
+            Some("io".to_string())
+        );
+        assert_eq!(super::module_name(&parse_quote! { std_defs::fmt }), None);
+        assert_eq!(super::module_name(&parse_quote! { std::fmt }), None);
+    }
+
+    #[test]
+    fn all_defs_are_valid() {
+        for module in MODULES {
+            let module_ident = syn::Ident::new(module, proc_macro2::Span::call_site());
+            let module_path: syn::Path = parse_quote! { thin_delegate::std_defs::#module_ident };
+            for trait_ in defs(module).unwrap() {
+                assert_eq!(trait_def(&module_path, &trait_.ident).unwrap(), trait_);
+
+                let trait_path = syn::Path::from(syn::PathSegment::from(trait_.ident.clone()));
+                let trait_data = TraitData::new(&FillDelegateArgs::default(), &trait_, trait_path);
+                trait_data.validate().unwrap();
+            }
+        }
+    }
+}
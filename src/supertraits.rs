@@ -0,0 +1,230 @@
+use crate::fill_delegate_args::{FillDelegateArgs, UnitVariants};
+use crate::generic_param_replacer::GenericParamReplacer;
+use crate::std_defs;
+use proc_macro2::TokenStream;
+use quote::quote;
+
+/// Auto traits and derivable traits in `std`. They are not filled by delegation.
+const AUTO_AND_DERIVE_TRAITS: &[&str] = &[
+    "Send",
+    "Sync",
+    "Sized",
+    "Unpin",
+    "UnwindSafe",
+    "RefUnwindSafe",
+    "Copy",
+    "Clone",
+    "Debug",
+    "Default",
+    "PartialEq",
+    "Eq",
+    "PartialOrd",
+    "Ord",
+    "Hash",
+];
+
+/// Traits in the prelude, which can be written without paths.
+const PRELUDE_TRAITS: &[&str] = &[
+    "AsMut",
+    "AsRef",
+    "DoubleEndedIterator",
+    "Drop",
+    "ExactSizeIterator",
+    "Extend",
+    "Fn",
+    "FnMut",
+    "FnOnce",
+    "From",
+    "FromIterator",
+    "Into",
+    "IntoIterator",
+    "Iterator",
+    "ToOwned",
+    "ToString",
+    "TryFrom",
+    "TryInto",
+];
+
+/// How to fill a supertrait.
+enum Supertrait {
+    /// Registered with `#[thin_delegate::register]`.
+    Registered,
+    /// Bundled in `thin_delegate::std_defs::<module>`.
+    StdDefs(&'static str),
+}
+
+/// Classifies supertrait `path`. Auto traits and derivable traits are skipped unless `included`.
+fn classify(path: &syn::Path, included: bool) -> syn::Result<Option<Supertrait>> {
+    let ident = &path.segments.last().unwrap().ident;
+    let is_bare = path.leading_colon.is_none() && path.segments.len() == 1;
+    let is_std = !is_bare
+        && ["std", "core", "alloc"]
+            .iter()
+            .any(|krate| path.segments[0].ident == krate);
+    let is_prelude = is_bare && PRELUDE_TRAITS.iter().any(|x| ident == x);
+
+    if !included && (is_bare || is_std) && AUTO_AND_DERIVE_TRAITS.iter().any(|x| ident == x) {
+        return Ok(None);
+    }
+    if is_std {
+        return Ok(std_defs::bundled_module_of(path).map(Supertrait::StdDefs));
+    }
+    // Bare names of traits bundled in `thin_delegate::std_defs`, e.g. `Display` imported by
+    // `use std::fmt::Display;`, are regarded as the ones in `std`.
+    if is_bare {
+        match std_defs::bundled_modules_of(ident)[..] {
+            [] if is_prelude => return Ok(None),
+            [] => {}
+            [module] => return Ok(Some(Supertrait::StdDefs(module))),
+            ref modules => {
+                return Err(syn::Error::new_spanned(
+                    path,
+                    format!(
+                        "supertrait `{ident}` is ambiguous; write it with path, e.g. `std::{}::{ident}`, or skip it by `with_supertraits(skip({ident}))`",
+                        modules[0],
+                    ),
+                ));
+            }
+        }
+    }
+    Ok(Some(Supertrait::Registered))
+}
+
+/// Generates `#[thin_delegate::fill_delegate] impl Supertrait for ...` for each supertrait of
+/// `trait_` for `with_supertraits = true`.
+///
+/// Supertraits in `std` are filled only if they are bundled in `thin_delegate::std_defs`, and auto
+/// traits and derivable traits are skipped unless they are in `with_supertraits(include(...))`.
+/// Ones in `with_supertraits(skip(...))` are also skipped.
+pub(crate) fn gen_supertrait_impls(
+    args: &FillDelegateArgs,
+    trait_: &syn::ItemTrait,
+    trait_path: &syn::Path,
+    impl_: &syn::ItemImpl,
+) -> syn::Result<Vec<TokenStream>> {
+    let generic_param_replacer = GenericParamReplacer::new(
+        &trait_.generics,
+        &trait_path.segments.last().unwrap().arguments,
+    )?;
+
+    let mut forwarded_args = vec![];
+    if let Some(path) = &args.external_structenum_def {
+        forwarded_args.push(quote! { external_structenum_def = #path });
+    }
+    if let Some(unit_variants) = args.unit_variants {
+        let unit_variants = match unit_variants {
+            UnitVariants::Unreachable => quote! { unreachable },
+            UnitVariants::Panic => quote! { panic },
+            UnitVariants::Default => quote! { default },
+        };
+        forwarded_args.push(quote! { unit_variants = #unit_variants });
+    }
+    if args.infer_bounds {
+        forwarded_args.push(quote! { infer_bounds = true });
+    }
+
+    let (impl_generics, _, where_clause) = impl_.generics.split_for_impl();
+    let self_ty = &impl_.self_ty;
+
+    for ident in args
+        .skip_supertraits
+        .iter()
+        .chain(&args.include_supertraits)
+    {
+        let is_supertrait = trait_.supertraits.iter().any(|bound| match bound {
+            syn::TypeParamBound::Trait(bound) => {
+                bound.path.segments.last().unwrap().ident == *ident
+            }
+            _ => false,
+        });
+        if !is_supertrait {
+            return Err(syn::Error::new_spanned(
+                ident,
+                format!("`{ident}` is not a supertrait of `{}`", trait_.ident),
+            ));
+        }
+    }
+
+    let mut impls = vec![];
+    for bound in &trait_.supertraits {
+        let syn::TypeParamBound::Trait(bound) = bound else {
+            continue;
+        };
+        if !matches!(bound.modifier, syn::TraitBoundModifier::None) {
+            continue;
+        }
+        let ident = &bound.path.segments.last().unwrap().ident;
+        if args.skip_supertraits.contains(ident) {
+            continue;
+        }
+        let included = args.include_supertraits.contains(ident);
+        let Some(supertrait) = classify(&bound.path, included)? else {
+            if included {
+                return Err(syn::Error::new_spanned(
+                    &bound.path,
+                    format!("supertrait `{ident}` can't be included as it is not bundled in `thin_delegate::std_defs`"),
+                ));
+            }
+            continue;
+        };
+        let path = syn::Type::Path(syn::TypePath {
+            qself: None,
+            path: bound.path.clone(),
+        });
+        let syn::Type::Path(path) = generic_param_replacer.replace_type(path) else {
+            panic!();
+        };
+        let path = path.path;
+        let mut args = forwarded_args.clone();
+        if let Supertrait::StdDefs(module) = supertrait {
+            let module = syn::Ident::new(module, proc_macro2::Span::call_site());
+            args.push(quote! { external_trait_def = ::thin_delegate::std_defs::#module });
+        }
+        impls.push(quote! {
+            #[::thin_delegate::fill_delegate(#(#args),*)]
+            impl #impl_generics #path for #self_ty #where_clause {}
+        });
+    }
+
+    Ok(impls)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use syn::parse_quote;
+
+    #[test]
+    fn classify() {
+        let classify_with = |path: syn::Path, included: bool| match super::classify(&path, included)
+        {
+            Err(_) => "<err>".to_string(),
+            Ok(None) => "skip".to_string(),
+            Ok(Some(Supertrait::Registered)) => "registered".to_string(),
+            Ok(Some(Supertrait::StdDefs(module))) => module.to_string(),
+        };
+        let classify = |path: syn::Path| classify_with(path, false);
+        assert_eq!(classify(parse_quote! { Hello }), "registered");
+        assert_eq!(classify(parse_quote! { crate::Hello<T> }), "registered");
+        assert_eq!(classify(parse_quote! { crate::Debug }), "registered");
+        assert_eq!(classify(parse_quote! { Send }), "skip");
+        assert_eq!(classify(parse_quote! { ::core::fmt::Debug }), "skip");
+        assert_eq!(classify(parse_quote! { ToString }), "skip");
+        assert_eq!(classify(parse_quote! { std::fmt::Display }), "fmt");
+        assert_eq!(classify(parse_quote! { Iterator }), "iter");
+        assert_eq!(classify(parse_quote! { ::std::io::Read }), "io");
+        assert_eq!(classify(parse_quote! { std::ops::Add }), "skip");
+        assert_eq!(classify(parse_quote! { std::fmt::Write }), "fmt");
+        assert_eq!(classify(parse_quote! { std::io::Write }), "io");
+        assert_eq!(classify(parse_quote! { Display }), "fmt");
+        assert_eq!(classify(parse_quote! { Error }), "error");
+        assert_eq!(classify(parse_quote! { Write }), "<err>");
+        assert_eq!(classify(parse_quote! { crate::Display }), "registered");
+
+        let include = |path: syn::Path| classify_with(path, true);
+        assert_eq!(include(parse_quote! { Debug }), "fmt");
+        assert_eq!(include(parse_quote! { std::hash::Hash }), "hash");
+        assert_eq!(include(parse_quote! { Clone }), "registered");
+        assert_eq!(include(parse_quote! { std::clone::Clone }), "skip");
+    }
+}
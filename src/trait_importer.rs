@@ -0,0 +1,489 @@
+use crate::external_trait_def_args::ExternalTraitDefArgs;
+use proc_macro2::{Group, Spacing, Span, TokenStream, TokenTree};
+use quote::quote;
+use std::collections::HashSet;
+use std::path::{Component, Path, PathBuf};
+use syn::parse_quote;
+
+/// Imports traits `traits(...)` from the file designated by `from_file`.
+///
+/// Returns items that would be pasted in the module of `#[thin_delegate::external_trait_def]`, i.e.
+/// `use`s the traits need and the traits qualified with `#[thin_delegate::register]`, and an item
+/// that makes rustc track changes of the file.
+pub(crate) fn import(
+    args: &ExternalTraitDefArgs,
+) -> syn::Result<Option<(Vec<syn::Item>, syn::Item)>> {
+    let Some(file) = &args.from_file else {
+        if !args.traits.is_empty() {
+            return Err(syn::Error::new(
+                Span::call_site(),
+                "`traits(...)` requires `from_file`",
+            ));
+        }
+        return Ok(None);
+    };
+    if args.traits.is_empty() {
+        return Err(syn::Error::new_spanned(
+            file,
+            "`traits(...)` is required for `from_file`",
+        ));
+    }
+
+    let relative_path = PathBuf::from(file.value());
+    let crate_path = match &args.crate_path {
+        Some(crate_path) => crate_path.clone(),
+        None => default_crate_path(&relative_path).ok_or_else(|| {
+            syn::Error::new_spanned(
+                file,
+                "can't infer the crate from the path; `crate_path = <path>` is required",
+            )
+        })?,
+    };
+    let mod_path = module_path_of(&relative_path);
+
+    let path = manifest_dir()?.join(&relative_path);
+    // Messages of `std::io::Error` depend on the platform. Use our own ones.
+    if !path.is_file() {
+        return Err(syn::Error::new_spanned(
+            file,
+            format!(
+                "file `{}` is not found in `CARGO_MANIFEST_DIR`",
+                file.value()
+            ),
+        ));
+    }
+    let content = std::fs::read_to_string(&path).map_err(|_| {
+        syn::Error::new_spanned(
+            file,
+            format!("failed to read `{}` as a UTF-8 text", file.value()),
+        )
+    })?;
+    let parsed = syn::parse_file(&content).map_err(|e| {
+        syn::Error::new_spanned(file, format!("failed to parse `{}`: {e}", file.value()))
+    })?;
+
+    let items = extract(&parsed.items, &args.traits, &crate_path, &mod_path).map_err(|ident| {
+        syn::Error::new_spanned(
+            ident,
+            format!("trait `{ident}` is not found in `{}`", file.value()),
+        )
+    })?;
+
+    // `include_bytes!()` lets rustc recompile the module if the file is changed.
+    let path = path.to_string_lossy();
+    let tracker = parse_quote! {
+        const _: &[u8] = ::core::include_bytes!(#path);
+    };
+
+    Ok(Some((items, tracker)))
+}
+
+fn manifest_dir() -> syn::Result<PathBuf> {
+    std::env::var_os("CARGO_MANIFEST_DIR")
+        .map(PathBuf::from)
+        .ok_or_else(|| syn::Error::new(Span::call_site(), "`CARGO_MANIFEST_DIR` is not set"))
+}
+
+/// Infers the crate from the path of a file, e.g. `::smithay` for
+/// `vendor/smithay/src/input/pointer/mod.rs`.
+fn default_crate_path(path: &Path) -> Option<syn::Path> {
+    let components = path
+        .components()
+        .filter_map(|component| match component {
+            Component::Normal(name) => name.to_str(),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    let i = components.iter().rposition(|name| *name == "src")?;
+    let name = components.get(i.checked_sub(1)?)?.replace('-', "_");
+    let ident = syn::parse_str::<syn::Ident>(&name).ok()?;
+    Some(parse_quote! { ::#ident })
+}
+
+/// Returns the module path of a file in the crate, e.g. `["input", "pointer"]` for
+/// `vendor/smithay/src/input/pointer/mod.rs`.
+fn module_path_of(path: &Path) -> Vec<String> {
+    let components = path
+        .components()
+        .filter_map(|component| match component {
+            Component::Normal(name) => name.to_str(),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    let start = components
+        .iter()
+        .rposition(|name| *name == "src")
+        .map_or(0, |i| i + 1);
+    let mut mod_path = components[start..]
+        .iter()
+        .map(|name| name.trim_end_matches(".rs").to_string())
+        .collect::<Vec<_>>();
+    if matches!(
+        mod_path.last().map(String::as_str),
+        Some("mod" | "lib" | "main")
+    ) {
+        mod_path.pop();
+    }
+    mod_path
+}
+
+/// Extracts `traits` and `use`s they need from `items`. `use`s are collected from the module where
+/// each trait is defined.
+///
+/// Returns the ident of a trait that is not found as an error.
+fn extract<'a>(
+    items: &[syn::Item],
+    traits: &'a [syn::Ident],
+    crate_path: &syn::Path,
+    mod_path: &[String],
+) -> Result<Vec<syn::Item>, &'a syn::Ident> {
+    let mut uses = vec![];
+    let mut seen_uses = HashSet::new();
+    let mut ret = vec![];
+    for ident in traits {
+        let Some((mut trait_, mod_items, trait_mod_path)) =
+            find_trait(items, ident, mod_path.to_vec())
+        else {
+            return Err(ident);
+        };
+        strip_attrs(&mut trait_);
+        let mut trait_ =
+            syn::parse2::<syn::ItemTrait>(replace_crate_root(quote! { #trait_ }, crate_path))
+                .unwrap();
+        let mut used_idents = HashSet::new();
+        collect_idents(quote! { #trait_ }, &mut used_idents);
+        trait_
+            .attrs
+            .push(parse_quote! { #[::thin_delegate::register] });
+        ret.push(syn::Item::Trait(trait_));
+
+        for item in mod_items {
+            let syn::Item::Use(use_) = item else {
+                continue;
+            };
+            if !is_needed(&use_.tree, &used_idents) {
+                continue;
+            }
+            let Some(mut use_) = rewrite_use(use_, crate_path, &trait_mod_path) else {
+                continue;
+            };
+            use_.attrs.retain(is_cfg_attr);
+            use_.attrs.push(parse_quote! { #[allow(unused_imports)] });
+            use_.vis = syn::Visibility::Inherited;
+            // Traits in the same module need the same `use`s.
+            if seen_uses.insert(quote! { #use_ }.to_string()) {
+                uses.push(syn::Item::Use(use_));
+            }
+        }
+
+        // Items defined in the module, e.g. `MotionEvent` for `PointerTarget`, are imported from
+        // `crate_path`. Traits imported together are registered in this module instead.
+        for item in mod_items {
+            let Some((item_ident, attrs)) = defined_item(item) else {
+                continue;
+            };
+            if !used_idents.contains(&item_ident.to_string()) || traits.contains(item_ident) {
+                continue;
+            }
+            let cfg_attrs = attrs.iter().filter(|attr| is_cfg_attr(attr));
+            let mod_idents = trait_mod_path
+                .iter()
+                .map(|name| syn::Ident::new(name, Span::call_site()));
+            let use_: syn::ItemUse = parse_quote! {
+                #(#cfg_attrs)*
+                #[allow(unused_imports)]
+                use #crate_path #(::#mod_idents)* ::#item_ident;
+            };
+            if seen_uses.insert(quote! { #use_ }.to_string()) {
+                uses.push(syn::Item::Use(use_));
+            }
+        }
+    }
+    uses.extend(ret);
+
+    Ok(uses)
+}
+
+/// Returns the ident and the attributes of `item` if it is a type, a trait or a constant visible
+/// from outside of the module.
+fn defined_item(item: &syn::Item) -> Option<(&syn::Ident, &[syn::Attribute])> {
+    let (vis, ident, attrs) = match item {
+        syn::Item::Struct(x) => (&x.vis, &x.ident, &x.attrs),
+        syn::Item::Enum(x) => (&x.vis, &x.ident, &x.attrs),
+        syn::Item::Union(x) => (&x.vis, &x.ident, &x.attrs),
+        syn::Item::Type(x) => (&x.vis, &x.ident, &x.attrs),
+        syn::Item::Trait(x) => (&x.vis, &x.ident, &x.attrs),
+        syn::Item::Const(x) => (&x.vis, &x.ident, &x.attrs),
+        _ => return None,
+    };
+    match vis {
+        syn::Visibility::Inherited => None,
+        _ => Some((ident, attrs)),
+    }
+}
+
+/// Finds trait `ident` in `items` of module `mod_path` and its nested modules.
+///
+/// Returns the trait, items of the module where it is defined and the path of the module.
+fn find_trait<'a>(
+    items: &'a [syn::Item],
+    ident: &syn::Ident,
+    mod_path: Vec<String>,
+) -> Option<(syn::ItemTrait, &'a [syn::Item], Vec<String>)> {
+    items.iter().find_map(|item| match item {
+        syn::Item::Trait(trait_) if trait_.ident == *ident => {
+            Some((trait_.clone(), items, mod_path.clone()))
+        }
+        syn::Item::Mod(syn::ItemMod {
+            ident: mod_ident,
+            content: Some((_, mod_items)),
+            ..
+        }) => {
+            let mut mod_path = mod_path.clone();
+            mod_path.push(mod_ident.to_string());
+            find_trait(mod_items, ident, mod_path)
+        }
+        _ => None,
+    })
+}
+
+/// Removes attributes except for documentation and `#[cfg(...)]`/`#[cfg_attr(...)]`, e.g.
+/// `#[stable(...)]` in `std`, and bodies of provided methods. Only the signatures matter.
+fn strip_attrs(trait_: &mut syn::ItemTrait) {
+    let is_kept = |attr: &syn::Attribute| attr.path().is_ident("doc") || is_cfg_attr(attr);
+    trait_.attrs.retain(is_kept);
+    for item in &mut trait_.items {
+        match item {
+            syn::TraitItem::Fn(fn_) => {
+                fn_.attrs.retain(is_kept);
+                if let Some(block) = &mut fn_.default {
+                    block.stmts.clear();
+                }
+            }
+            syn::TraitItem::Type(type_) => type_.attrs.retain(is_kept),
+            syn::TraitItem::Const(const_) => const_.attrs.retain(is_kept),
+            _ => {}
+        }
+    }
+}
+
+fn is_cfg_attr(attr: &syn::Attribute) -> bool {
+    attr.path().is_ident("cfg") || attr.path().is_ident("cfg_attr")
+}
+
+/// Replaces paths `crate::...` with `#crate_path::...`.
+fn replace_crate_root(tokens: TokenStream, crate_path: &syn::Path) -> TokenStream {
+    let tokens = tokens.into_iter().collect::<Vec<_>>();
+    let mut ret = TokenStream::new();
+    for (i, tt) in tokens.iter().enumerate() {
+        match tt {
+            TokenTree::Group(group) => {
+                let mut new_group = Group::new(
+                    group.delimiter(),
+                    replace_crate_root(group.stream(), crate_path),
+                );
+                new_group.set_span(group.span());
+                ret.extend([TokenTree::Group(new_group)]);
+            }
+            TokenTree::Ident(ident) if ident == "crate" => {
+                let is_path_root = matches!(
+                    (tokens.get(i + 1), tokens.get(i + 2)),
+                    (Some(TokenTree::Punct(p1)), Some(TokenTree::Punct(p2)))
+                        if p1.as_char() == ':' && p1.spacing() == Spacing::Joint && p2.as_char() == ':'
+                );
+                if is_path_root {
+                    ret.extend(quote! { #crate_path });
+                } else {
+                    ret.extend([tt.clone()]);
+                }
+            }
+            _ => {
+                ret.extend([tt.clone()]);
+            }
+        }
+    }
+    ret
+}
+
+fn collect_idents(tokens: TokenStream, idents: &mut HashSet<String>) {
+    for tt in tokens {
+        match tt {
+            TokenTree::Ident(ident) => {
+                idents.insert(ident.to_string());
+            }
+            TokenTree::Group(group) => collect_idents(group.stream(), idents),
+            _ => {}
+        }
+    }
+}
+
+/// Returns true if `tree` imports a name in `used_idents` or it is a glob import.
+fn is_needed(tree: &syn::UseTree, used_idents: &HashSet<String>) -> bool {
+    match tree {
+        syn::UseTree::Path(path) => is_needed(&path.tree, used_idents),
+        syn::UseTree::Name(name) => used_idents.contains(&name.ident.to_string()),
+        syn::UseTree::Rename(rename) => used_idents.contains(&rename.rename.to_string()),
+        syn::UseTree::Glob(_) => true,
+        syn::UseTree::Group(group) => group.items.iter().any(|tree| is_needed(tree, used_idents)),
+    }
+}
+
+/// Rewrites `crate::`, `self::` and `super::` in `use_` to paths from `crate_path`.
+///
+/// Returns `None` if `super::` goes beyond the crate root.
+fn rewrite_use(
+    use_: &syn::ItemUse,
+    crate_path: &syn::Path,
+    mod_path: &[String],
+) -> Option<syn::ItemUse> {
+    if use_.leading_colon.is_some() {
+        return Some(use_.clone());
+    }
+
+    let mut base = None;
+    let mut tree = &use_.tree;
+    while let syn::UseTree::Path(path) = tree {
+        match (path.ident.to_string().as_str(), &mut base) {
+            ("crate", None) => base = Some(vec![]),
+            ("self", None) => base = Some(mod_path.to_vec()),
+            ("super", None) => {
+                let mut mod_path = mod_path.to_vec();
+                mod_path.pop()?;
+                base = Some(mod_path);
+            }
+            ("super", Some(base)) => {
+                base.pop()?;
+            }
+            _ => break,
+        }
+        tree = &path.tree;
+    }
+    let Some(base) = base else {
+        return Some(use_.clone());
+    };
+
+    let base = base
+        .iter()
+        .map(|name| syn::Ident::new(name, Span::call_site()));
+    Some(parse_quote! {
+        use #crate_path #(::#base)* :: #tree;
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_crate_path() {
+        let path = Path::new("vendor/smithay/src/input/pointer/mod.rs");
+        let expected: syn::Path = parse_quote! { ::smithay };
+        assert_eq!(super::default_crate_path(path), Some(expected));
+
+        let path = Path::new("vendor/wayland-server/src/lib.rs");
+        let expected: syn::Path = parse_quote! { ::wayland_server };
+        assert_eq!(super::default_crate_path(path), Some(expected));
+
+        assert_eq!(super::default_crate_path(Path::new("src/lib.rs")), None);
+        assert_eq!(super::default_crate_path(Path::new("hoge.rs")), None);
+    }
+
+    #[test]
+    fn module_path_of() {
+        let path = Path::new("vendor/smithay/src/input/pointer/mod.rs");
+        assert_eq!(super::module_path_of(path), vec!["input", "pointer"]);
+
+        let path = Path::new("core/src/fmt/builders.rs");
+        assert_eq!(super::module_path_of(path), vec!["fmt", "builders"]);
+
+        let path = Path::new("vendor/smithay/src/lib.rs");
+        assert!(super::module_path_of(path).is_empty());
+    }
+
+    #[test]
+    fn extract() {
+        let file: syn::File = parse_quote! {
+            use crate::utils::Serial;
+            use super::Seat;
+            use self::grab::{GrabStartData, PointerGrab};
+            use std::fmt;
+
+            #[stable(feature = "rust1", since = "1.0.0")]
+            /// A target.
+            pub trait PointerTarget<D>: Send {
+                #[must_use]
+                fn enter(&self, seat: &Seat<D>, serial: Serial);
+                fn leave(&self, seat: &Seat<D>) {
+                    unreachable!()
+                }
+                fn frame(&self, data: &crate::utils::Frame);
+                fn motion(&self, event: &MotionEvent, private: Private);
+                fn axis(&self, frame: AxisFrame);
+            }
+
+            pub trait Unrelated {}
+
+            pub struct MotionEvent;
+            #[cfg(feature = "axis")]
+            pub struct AxisFrame;
+            struct Private;
+
+            mod inner {
+                use super::grab::GrabStartData;
+                use std::collections::BTreeMap;
+
+                pub trait Grab {
+                    fn start_data(&self) -> &GrabStartData;
+                    #[cfg(feature = "map")]
+                    #[must_use]
+                    fn map(&self) -> BTreeMap<u32, u32>;
+                }
+            }
+        };
+        let traits = [parse_quote! { PointerTarget }, parse_quote! { Grab }];
+        let crate_path = parse_quote! { ::smithay };
+        let mod_path = ["input".to_string(), "pointer".to_string()];
+        let expected: syn::File = parse_quote! {
+            #[allow(unused_imports)]
+            use ::smithay::utils::Serial;
+            #[allow(unused_imports)]
+            use ::smithay::input::Seat;
+            #[allow(unused_imports)]
+            use ::smithay::input::pointer::MotionEvent;
+            #[cfg(feature = "axis")]
+            #[allow(unused_imports)]
+            use ::smithay::input::pointer::AxisFrame;
+            #[allow(unused_imports)]
+            use ::smithay::input::pointer::grab::GrabStartData;
+            #[allow(unused_imports)]
+            use std::collections::BTreeMap;
+
+            /// A target.
+            #[::thin_delegate::register]
+            pub trait PointerTarget<D>: Send {
+                fn enter(&self, seat: &Seat<D>, serial: Serial);
+                fn leave(&self, seat: &Seat<D>) {}
+                fn frame(&self, data: &::smithay::utils::Frame);
+                fn motion(&self, event: &MotionEvent, private: Private);
+                fn axis(&self, frame: AxisFrame);
+            }
+
+            #[::thin_delegate::register]
+            pub trait Grab {
+                fn start_data(&self) -> &GrabStartData;
+                #[cfg(feature = "map")]
+                fn map(&self) -> BTreeMap<u32, u32>;
+            }
+        };
+        assert_eq!(
+            super::extract(&file.items, &traits, &crate_path, &mod_path).unwrap(),
+            expected.items,
+        );
+
+        let traits = [parse_quote! { Hoge }];
+        assert_eq!(
+            super::extract(&file.items, &traits, &crate_path, &mod_path).unwrap_err(),
+            &traits[0],
+        );
+    }
+}
@@ -0,0 +1,200 @@
+use proc_macro2::TokenStream;
+use quote::{quote, quote_spanned};
+use syn::parse_quote;
+
+/// Generates an item that fails to compile if a copy of trait `trait_` doesn't match with the real
+/// trait `#real_mod::#trait_ident`.
+///
+/// It implements the real trait for a dummy type with the signatures in the copy:
+///
+/// ```text
+/// const _: () = {
+///     struct __ThinDelegateVerify<__ThinDelegateT, D>(PhantomData<(__ThinDelegateT, D)>);
+///
+///     impl<__ThinDelegateT, D> real::Hello<D> for __ThinDelegateVerify<__ThinDelegateT, D>
+///     where
+///         D: Clone,
+///         Self: SuperTrait,
+///     {
+///         fn hello(&self, arg: D) -> String {
+///             unimplemented!()
+///         }
+///
+///         // With `where __ThinDelegateT: real::Hello<D>` added to the where clause.
+///         fn items(&self) -> impl Iterator<Item = D> {
+///             <__ThinDelegateT as real::Hello<D>>::items(unimplemented!())
+///         }
+///     }
+/// };
+/// ```
+///
+/// So rustc reports methods that don't exist in the real trait, methods with different signatures,
+/// and required methods missing in the copy. As tokens of the copy are used, errors point the copy.
+pub(crate) fn gen_verification(trait_: &syn::ItemTrait, real_mod: &syn::Path) -> TokenStream {
+    let span = trait_.ident.span();
+    let verify = syn::Ident::new("__ThinDelegateVerify", span);
+
+    // Parameters of the impl. The dummy parameter prevents where clauses from being trivial.
+    let mut generics = trait_.generics.clone();
+    for param in &mut generics.params {
+        match param {
+            syn::GenericParam::Type(param) => {
+                param.eq_token = None;
+                param.default = None;
+            }
+            syn::GenericParam::Const(param) => {
+                param.eq_token = None;
+                param.default = None;
+            }
+            syn::GenericParam::Lifetime(_) => {}
+        }
+    }
+    let num_lifetimes = generics.lifetimes().count();
+    generics
+        .params
+        .insert(num_lifetimes, parse_quote! { __ThinDelegateT });
+
+    let trait_ident = &trait_.ident;
+    let (_, trait_generics, _) = trait_.generics.split_for_impl();
+    let mut needs_real_impl = false;
+    let mut items = vec![];
+    for item in &trait_.items {
+        match item {
+            syn::TraitItem::Fn(fn_) if returns_impl_trait(&fn_.sig) => {
+                // A diverging body can't satisfy `impl Trait` as its type falls back to `()`. So it
+                // returns what the real trait returns for `__ThinDelegateT`.
+                let sig = &fn_.sig;
+                let ident = &sig.ident;
+                let args = sig.inputs.iter().map(|arg| match arg {
+                    syn::FnArg::Typed(pat_type) => match pat_type.pat.as_ref() {
+                        syn::Pat::Ident(pat) => {
+                            let ident = &pat.ident;
+                            quote! { #ident }
+                        }
+                        _ => quote! { ::core::unimplemented!() },
+                    },
+                    syn::FnArg::Receiver(_) => quote! { ::core::unimplemented!() },
+                });
+                let call = quote_spanned! {sig.ident.span()=>
+                    <__ThinDelegateT as #real_mod::#trait_ident #trait_generics>::#ident(#(#args),*)
+                };
+                let call = match sig.unsafety {
+                    Some(_) => quote_spanned! {sig.ident.span()=> unsafe { #call } },
+                    None => call,
+                };
+                items.push(quote_spanned! {sig.ident.span()=>
+                    #sig {
+                        #call
+                    }
+                });
+                needs_real_impl = true;
+            }
+            syn::TraitItem::Fn(fn_) => {
+                // Spans of the body also matter because some errors point the whole item.
+                let sig = &fn_.sig;
+                items.push(quote_spanned! {sig.ident.span()=>
+                    #sig {
+                        ::core::unimplemented!()
+                    }
+                });
+            }
+            syn::TraitItem::Const(const_) => {
+                let ident = &const_.ident;
+                let ty = &const_.ty;
+                items.push(quote! {
+                    const #ident: #ty = ::core::unimplemented!();
+                });
+            }
+            syn::TraitItem::Type(type_) if type_.generics.params.is_empty() => {
+                // Use a parameter so that it satisfies the bounds.
+                let ident = &type_.ident;
+                let param = syn::Ident::new(&format!("__ThinDelegateAssoc{ident}"), ident.span());
+                let bounds = &type_.bounds;
+                generics.params.push(parse_quote! { #param: #bounds });
+                items.push(quote! {
+                    type #ident = #param;
+                });
+            }
+            syn::TraitItem::Type(type_) => {
+                // GATs can't be parameters. Only their existence is verified.
+                let ident = &type_.ident;
+                let params = &type_.generics.params;
+                let where_clause = &type_.generics.where_clause;
+                items.push(quote! {
+                    type #ident<#params> = () #where_clause;
+                });
+            }
+            _ => {}
+        }
+    }
+
+    let mut where_clause = generics.make_where_clause().clone();
+    if needs_real_impl {
+        where_clause
+            .predicates
+            .push(parse_quote! { __ThinDelegateT: #real_mod::#trait_ident #trait_generics });
+    }
+    let supertraits = &trait_.supertraits;
+    if !supertraits.is_empty() {
+        where_clause
+            .predicates
+            .push(parse_quote! { Self: #supertraits });
+    }
+
+    let phantom_types = generics.params.iter().filter_map(|param| match param {
+        syn::GenericParam::Lifetime(param) => {
+            let lifetime = &param.lifetime;
+            Some(quote! { &#lifetime () })
+        }
+        syn::GenericParam::Type(param) => {
+            let ident = &param.ident;
+            Some(quote! { #ident })
+        }
+        syn::GenericParam::Const(_) => None,
+    });
+    let struct_params = generics.params.iter().map(|param| match param {
+        syn::GenericParam::Lifetime(param) => {
+            let lifetime = &param.lifetime;
+            quote! { #lifetime }
+        }
+        syn::GenericParam::Type(param) => {
+            let ident = &param.ident;
+            quote! { #ident }
+        }
+        syn::GenericParam::Const(param) => {
+            let ident = &param.ident;
+            let ty = &param.ty;
+            quote! { const #ident: #ty }
+        }
+    });
+    let (impl_generics, self_generics, _) = generics.split_for_impl();
+    let unsafety = &trait_.unsafety;
+
+    quote_spanned! {span=>
+        #[allow(unused)]
+        const _: () = {
+            struct #verify<#(#struct_params),*>(
+                ::core::marker::PhantomData<(#(#phantom_types,)*)>
+            );
+
+            #unsafety impl #impl_generics #real_mod::#trait_ident #trait_generics for #verify #self_generics
+            #where_clause
+            {
+                #(#items)*
+            }
+        };
+    }
+}
+
+/// Returns true if the return type of `sig` contains `impl Trait`.
+fn returns_impl_trait(sig: &syn::Signature) -> bool {
+    fn contains_impl(tokens: TokenStream) -> bool {
+        tokens.into_iter().any(|token| match token {
+            proc_macro2::TokenTree::Ident(ident) => ident == "impl",
+            proc_macro2::TokenTree::Group(group) => contains_impl(group.stream()),
+            _ => false,
+        })
+    }
+    let output = &sig.output;
+    contains_impl(quote! { #output })
+}
@@ -0,0 +1,34 @@
+//! Registrations exported by `#[thin_delegate::register(export)]` for
+//! `tests/ui/pass_register_export_cross_crate.rs`.
+
+pub mod core_api {
+    pub struct ExportedArg;
+
+    #[thin_delegate::register(export)]
+    pub trait ExportedHello {
+        fn hello(&self, arg: crate::core_api::ExportedArg) -> String;
+    }
+
+    impl ExportedHello for String {
+        fn hello(&self, _arg: crate::core_api::ExportedArg) -> String {
+            format!("hello, {self}")
+        }
+    }
+
+    #[thin_delegate::register(export)]
+    pub struct ExportedName(pub String);
+
+    #[thin_delegate::register(export)]
+    pub enum ExportedShape {
+        Circle(crate::core_api::ExportedCircle),
+        Square(crate::core_api::ExportedSquare),
+    }
+
+    pub struct ExportedCircle {
+        pub r: f64,
+    }
+
+    pub struct ExportedSquare {
+        pub a: f64,
+    }
+}
@@ -0,0 +1,79 @@
+#[thin_delegate::register]
+pub trait Hello {
+    fn hello(&self) -> String;
+    fn bye(&self) -> String {
+        "bye".to_string()
+    }
+}
+
+impl Hello for String {
+    fn hello(&self) -> String {
+        format!("hello, {self}")
+    }
+}
+
+#[thin_delegate::register]
+struct Hoge(String);
+
+// Only for enum.
+#[thin_delegate::fill_delegate]
+impl Hello for Hoge {
+    #[thin_delegate::arm(Self(s))]
+    fn hello(&self) -> String {
+        s.clone()
+    }
+}
+
+#[thin_delegate::register]
+enum Fuga {
+    A(String),
+}
+
+// `bye` is not filled.
+#[thin_delegate::fill_delegate]
+impl Hello for Fuga {
+    #[thin_delegate::arm(Self::A(s))]
+    fn bye(&self) -> String {
+        s.clone()
+    }
+}
+
+#[thin_delegate::register]
+enum Piyo {
+    A(String),
+}
+
+// `hello` is implemented.
+#[thin_delegate::fill_delegate]
+impl Hello for Piyo {
+    #[thin_delegate::arm(Self::A(s))]
+    fn hello(&self) -> String {
+        s.clone()
+    }
+
+    fn hello(&self) -> String {
+        "hello".to_string()
+    }
+}
+
+#[thin_delegate::register]
+enum Hogera {
+    A(String),
+    B(String),
+}
+
+// Overrides of `hello` have different signatures.
+#[thin_delegate::fill_delegate]
+impl Hello for Hogera {
+    #[thin_delegate::arm(Self::A(s))]
+    fn hello(&self) -> String {
+        s.clone()
+    }
+
+    #[thin_delegate::arm(Self::B(s))]
+    fn hello(&mut self) -> String {
+        s.clone()
+    }
+}
+
+fn main() {}
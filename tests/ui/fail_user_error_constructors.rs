@@ -0,0 +1,73 @@
+#[thin_delegate::register]
+pub trait Surface {
+    fn new(name: String) -> Self;
+    fn name(&self) -> String;
+}
+
+#[thin_delegate::register]
+pub trait Named {
+    fn kind() -> String;
+    fn name(&self) -> String;
+}
+
+impl Surface for String {
+    fn new(name: String) -> Self {
+        name
+    }
+
+    fn name(&self) -> String {
+        self.clone()
+    }
+}
+
+impl Named for String {
+    fn kind() -> String {
+        "string".to_string()
+    }
+
+    fn name(&self) -> String {
+        self.clone()
+    }
+}
+
+// The variant to construct is unknown.
+#[thin_delegate::register]
+pub enum AnySurface {
+    A(String),
+    B(String),
+}
+
+#[thin_delegate::fill_delegate]
+impl Surface for AnySurface {}
+
+// Not a variant.
+#[thin_delegate::fill_delegate(constructor_variant = C)]
+impl Surface for AnySurface {}
+
+// Other fields can't be filled.
+#[thin_delegate::register]
+pub struct Hoge {
+    #[thin_delegate::delegate]
+    name: String,
+    id: usize,
+}
+
+#[thin_delegate::fill_delegate]
+impl Surface for Hoge {}
+
+// Not returning `Self`.
+#[thin_delegate::register]
+pub struct Fuga(String);
+
+#[thin_delegate::fill_delegate]
+impl Named for Fuga {}
+
+// Implementing it manually is fine.
+#[thin_delegate::fill_delegate]
+impl Named for AnySurface {
+    fn kind() -> String {
+        "any".to_string()
+    }
+}
+
+fn main() {}
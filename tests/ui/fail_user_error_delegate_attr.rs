@@ -0,0 +1,29 @@
+#[thin_delegate::register]
+pub trait Hello {
+    fn hello(&self) -> String;
+}
+
+impl Hello for String {
+    fn hello(&self) -> String {
+        format!("hello, {self}")
+    }
+}
+
+// Multiple fields are marked for the same trait.
+#[thin_delegate::register]
+struct Hoge(
+    #[thin_delegate::delegate] String,
+    #[thin_delegate::delegate(Hello)] String,
+);
+
+#[thin_delegate::fill_delegate]
+impl Hello for Hoge {}
+
+// No field is marked for the trait.
+#[thin_delegate::register]
+struct Fuga(#[thin_delegate::delegate(Bye)] String, String);
+
+#[thin_delegate::fill_delegate]
+impl Hello for Fuga {}
+
+fn main() {}
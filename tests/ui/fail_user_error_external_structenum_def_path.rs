@@ -0,0 +1,13 @@
+mod copies {
+    // `path` is required for a struct/enum.
+    #[thin_delegate::external_structenum_def]
+    #[thin_delegate::register]
+    pub struct Wrapper {
+        pub inner: String,
+    }
+}
+
+#[thin_delegate::external_structenum_def(path = crate::Hoge)]
+mod __external_structenum_def {}
+
+fn main() {}
@@ -0,0 +1,10 @@
+#[thin_delegate::external_trait_def(from_file = "tests/ui/vendor/upstream/src/not_found.rs", traits(Hello))]
+mod __external_trait_def_not_found {}
+
+#[thin_delegate::external_trait_def(from_file = "tests/ui/vendor/upstream/src/hello.rs")]
+mod __external_trait_def_no_traits {}
+
+#[thin_delegate::external_trait_def(traits(Hello))]
+mod __external_trait_def_no_file {}
+
+fn main() {}
@@ -0,0 +1,10 @@
+#[thin_delegate::external_trait_def]
+#[thin_delegate::register]
+pub trait Hello {
+    fn hello(&self) -> String;
+}
+
+#[thin_delegate::external_trait_def(path = crate::Hi)]
+mod __external_trait_def {}
+
+fn main() {}
@@ -0,0 +1,38 @@
+// Compare with pass_external_trait_def_verify.rs
+
+mod external {
+    pub struct Arg;
+
+    pub trait Hello {
+        fn hello(&self, arg: &Arg) -> String;
+
+        fn hi(&self) -> String;
+    }
+
+    pub trait Items {
+        fn items(&self) -> impl Iterator<Item = u32>;
+    }
+}
+
+#[thin_delegate::external_trait_def(with_uses = true, verify = crate::external)]
+mod __external_trait_def {
+    use crate::external::Arg;
+
+    // `hi()` is missing.
+    #[thin_delegate::register]
+    pub trait Hello {
+        // The signature is stale.
+        fn hello(&self, arg: Arg) -> String;
+
+        // Not in the real trait.
+        fn bye(&self) -> String;
+    }
+
+    #[thin_delegate::register]
+    pub trait Items {
+        // The item type is stale.
+        fn items(&self) -> impl Iterator<Item = u64>;
+    }
+}
+
+fn main() {}
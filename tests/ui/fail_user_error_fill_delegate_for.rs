@@ -0,0 +1,36 @@
+#[thin_delegate::register]
+pub trait Focus {
+    fn focus_id(&self) -> u32;
+}
+
+impl Focus for u32 {
+    fn focus_id(&self) -> u32 {
+        *self
+    }
+}
+
+#[thin_delegate::register]
+pub struct XdgShellFocus(u32);
+
+pub struct NotRegistered(u32);
+
+#[thin_delegate::register]
+pub struct TwoFields(u32, u32);
+
+// Each failing target is reported with its own span.
+thin_delegate::fill_delegate_for! {
+    impl Focus for XdgShellFocus, NotRegistered, TwoFields
+}
+
+// Unknown attribute.
+thin_delegate::fill_delegate_for! {
+    #[delegate(scheme = |f| f(&self.0))]
+    impl Focus for XdgShellFocus
+}
+
+// Missing target.
+thin_delegate::fill_delegate_for! {
+    impl Focus for
+}
+
+fn main() {}
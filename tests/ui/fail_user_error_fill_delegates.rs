@@ -0,0 +1,26 @@
+#[thin_delegate::register]
+pub trait Hello {
+    fn hello(&self) -> String;
+}
+
+impl Hello for String {
+    fn hello(&self) -> String {
+        format!("hello, {self}")
+    }
+}
+
+// `traits(...)` is missing.
+#[thin_delegate::register]
+#[thin_delegate::fill_delegates(scheme = |f| f(&self.0))]
+struct Hoge(String);
+
+// Malformed argument for a trait.
+#[thin_delegate::register]
+#[thin_delegate::fill_delegates(traits(Hello { skip = hello }))]
+struct Fuga(String);
+
+// Not a struct/enum.
+#[thin_delegate::fill_delegates(traits(Hello))]
+fn piyo() {}
+
+fn main() {}
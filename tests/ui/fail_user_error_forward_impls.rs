@@ -0,0 +1,43 @@
+// `&mut self` and `self` can't be forwarded through `&T` and `Rc<T>`.
+#[thin_delegate::register(forward_impls(ref, rc))]
+pub trait Counter {
+    fn get(&self) -> usize;
+    fn increment(&mut self);
+    fn into_inner(self) -> usize;
+}
+
+// Methods returning `Self` can't be forwarded.
+#[thin_delegate::register(forward_impls(box))]
+pub trait Duplicate {
+    fn duplicate(&self) -> Self;
+}
+
+#[thin_delegate::register(forward_impls(ref))]
+pub struct Hoge(usize);
+
+#[thin_delegate::register(forward_impls(box, cow))]
+pub trait Hello {
+    fn hello(&self) -> String;
+}
+
+// Methods with default implementation that can't be forwarded must be in `keep_default_impl`.
+#[thin_delegate::register(forward_impls(ref))]
+pub trait Named {
+    fn name(&self) -> String;
+    fn rename(&mut self, name: String) {
+        let _ = name;
+    }
+}
+
+// Only methods with default implementation can be given.
+#[thin_delegate::register(forward_impls(ref), keep_default_impl(get, missing))]
+pub trait Getter {
+    fn get(&self) -> usize;
+}
+
+#[thin_delegate::register(keep_default_impl(reset))]
+pub trait Reset {
+    fn reset(&mut self) {}
+}
+
+fn main() {}
@@ -0,0 +1,36 @@
+#[thin_delegate::register]
+pub trait Hello {
+    fn hello(&self) -> String;
+    fn name(&self) -> String;
+}
+
+impl Hello for String {
+    fn hello(&self) -> String {
+        format!("hello, {self}")
+    }
+
+    fn name(&self) -> String {
+        self.clone()
+    }
+}
+
+#[thin_delegate::register]
+struct Wrapper<T>(T);
+
+#[thin_delegate::fill_delegate(infer_bounds = true)]
+impl<T> Hello for Wrapper<T> {}
+
+#[thin_delegate::register]
+struct Hoge<T>(T);
+
+// Nothing is inferred with schemes.
+#[thin_delegate::fill_delegate(
+    infer_bounds = true,
+    scheme(name) = |f| f(&self.0),
+)]
+impl<T> Hello for Hoge<T> {}
+
+fn main() {
+    // The inferred bound `u32: Hello` is not satisfied.
+    let _ = Wrapper(42_u32).hello();
+}
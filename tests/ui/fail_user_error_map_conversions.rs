@@ -0,0 +1,25 @@
+#[thin_delegate::register]
+pub trait Hello {
+    fn hello(&self, name: &str) -> String;
+}
+
+impl Hello for String {
+    fn hello(&self, name: &str) -> String {
+        format!("{self}, {name}")
+    }
+}
+
+#[thin_delegate::register]
+struct Hoge(String);
+
+// Unknown argument.
+#[thin_delegate::fill_delegate(map_arg(nam = Into::into))]
+impl Hello for Hoge {}
+
+#[thin_delegate::register]
+struct Fuga(String);
+
+#[thin_delegate::fill_delegate(map_arg(name = Into::into, name = From::from))]
+impl Hello for Fuga {}
+
+fn main() {}
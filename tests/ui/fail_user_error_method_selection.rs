@@ -0,0 +1,35 @@
+#[thin_delegate::register]
+trait Hello {
+    fn hello(&self) -> String;
+    fn bye(&self) -> String {
+        "bye".to_string()
+    }
+}
+
+impl Hello for String {
+    fn hello(&self) -> String {
+        format!("hello, {self}")
+    }
+}
+
+#[thin_delegate::register]
+struct Hoge(String);
+
+// Unknown method.
+#[thin_delegate::fill_delegate(skip(helo))]
+impl Hello for Hoge {}
+
+#[thin_delegate::register]
+struct Fuga(String);
+
+// `hello` doesn't have default implementation.
+#[thin_delegate::fill_delegate(with_default_impl(hello))]
+impl Hello for Fuga {}
+
+#[thin_delegate::register]
+struct Piyo(String);
+
+#[thin_delegate::fill_delegate(skip(hello), only(bye))]
+impl Hello for Piyo {}
+
+fn main() {}
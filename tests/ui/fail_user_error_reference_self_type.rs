@@ -0,0 +1,20 @@
+// `impl <Trait> for &<Type>` is supported only for `thin_delegate::std_defs::ops`.
+
+#[thin_delegate::register]
+trait Hello {
+    fn hello(&self) -> String;
+}
+
+impl Hello for String {
+    fn hello(&self) -> String {
+        format!("hello, {self}")
+    }
+}
+
+#[thin_delegate::register]
+struct Hoge(String);
+
+#[thin_delegate::fill_delegate]
+impl Hello for &Hoge {}
+
+fn main() {}
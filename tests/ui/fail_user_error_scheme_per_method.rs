@@ -0,0 +1,31 @@
+#[thin_delegate::register]
+pub trait Hello {
+    fn hello(&self) -> String;
+    fn bye(&self) -> String;
+}
+
+impl Hello for String {
+    fn hello(&self) -> String {
+        format!("hello, {self}")
+    }
+
+    fn bye(&self) -> String {
+        format!("bye, {self}")
+    }
+}
+
+#[thin_delegate::register]
+struct Hoge(String);
+
+// Unknown method.
+#[thin_delegate::fill_delegate(scheme(hello, helo) = |f| f(&self.0))]
+impl Hello for Hoge {}
+
+#[thin_delegate::register]
+struct Fuga(String);
+
+// Multiple schemes for a method.
+#[thin_delegate::fill_delegate(scheme(hello, bye) = |f| f(&self.0), scheme(bye) = |f| f(&self.0))]
+impl Hello for Fuga {}
+
+fn main() {}
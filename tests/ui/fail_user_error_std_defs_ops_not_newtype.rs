@@ -0,0 +1,18 @@
+use std::ops::Add;
+
+#[thin_delegate::register]
+struct Point(f64, f64);
+
+#[thin_delegate::fill_delegate(external_trait_def = thin_delegate::std_defs::ops)]
+impl Add for Point {}
+
+#[thin_delegate::register]
+enum Number {
+    Int(i64),
+    Float(f64),
+}
+
+#[thin_delegate::fill_delegate(external_trait_def = thin_delegate::std_defs::ops)]
+impl Add for Number {}
+
+fn main() {}
@@ -0,0 +1,15 @@
+use std::fmt::Display;
+
+#[thin_delegate::register]
+struct Name(String);
+
+#[thin_delegate::fill_delegate(external_trait_def = thin_delegate::std_defs::convert)]
+impl Display for Name {}
+
+#[thin_delegate::register]
+struct Title(String);
+
+#[thin_delegate::fill_delegate(external_trait_def = thin_delegate::std_defs::fmtt)]
+impl Display for Title {}
+
+fn main() {}
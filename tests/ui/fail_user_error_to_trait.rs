@@ -0,0 +1,74 @@
+mod other {
+    #[thin_delegate::register]
+    pub trait RenderLegacy {
+        fn draw_frame(&mut self, scale: u32) -> String;
+        fn resize(&mut self, width: u32);
+    }
+}
+
+#[thin_delegate::register]
+pub trait Render {
+    fn render(&self, scale: u32) -> String;
+    fn resize(&mut self, width: u32);
+}
+
+#[thin_delegate::register]
+pub trait RenderMut {
+    fn render(&mut self, scale: u64) -> String;
+}
+
+#[thin_delegate::register]
+pub trait ResizeChecked {
+    fn resize(&mut self, width: u32) -> bool;
+}
+
+#[thin_delegate::register]
+struct Hoge(String);
+
+// Unknown method.
+#[thin_delegate::fill_delegate(rename(rendr = draw_frame))]
+impl Render for Hoge {}
+
+#[thin_delegate::register]
+struct Fuga(String);
+
+// Missing in `to_trait`.
+#[thin_delegate::fill_delegate(to_trait = other::RenderLegacy, verify_to_trait = true)]
+impl Render for Fuga {}
+
+#[thin_delegate::register]
+struct Piyo(String);
+
+// Receiver mismatch.
+#[thin_delegate::fill_delegate(
+    to_trait = other::RenderLegacy,
+    rename(render = draw_frame),
+    verify_to_trait = true,
+)]
+impl Render for Piyo {}
+
+#[thin_delegate::register]
+struct Foo(String);
+
+// Argument type mismatch.
+#[thin_delegate::fill_delegate(
+    to_trait = other::RenderLegacy,
+    rename(render = draw_frame),
+    verify_to_trait = true,
+)]
+impl RenderMut for Foo {}
+
+#[thin_delegate::register]
+struct Bar(String);
+
+// Return type mismatch.
+#[thin_delegate::fill_delegate(to_trait = other::RenderLegacy, verify_to_trait = true)]
+impl ResizeChecked for Bar {}
+
+#[thin_delegate::register]
+struct Hogera(String);
+
+#[thin_delegate::fill_delegate(verify_to_trait = true)]
+impl Render for Hogera {}
+
+fn main() {}
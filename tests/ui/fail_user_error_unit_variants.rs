@@ -0,0 +1,50 @@
+#[thin_delegate::register]
+pub trait Hello {
+    fn hello(&self) -> String;
+    fn len(&self) -> usize;
+}
+
+impl Hello for String {
+    fn hello(&self) -> String {
+        format!("hello, {self}")
+    }
+
+    fn len(&self) -> usize {
+        String::len(self)
+    }
+}
+
+// `lenght` is not a method of `Hello`.
+#[thin_delegate::register]
+enum Target {
+    Window(String),
+    #[thin_delegate::unit_variant(hello = "nobody".into(), lenght = 0)]
+    Nobody,
+}
+
+#[thin_delegate::fill_delegate]
+impl Hello for Target {}
+
+// The expression for all methods can't have the types of all methods.
+#[thin_delegate::register]
+enum Focus {
+    Window(String),
+    #[thin_delegate::unit_variant("nobody".to_string())]
+    Nobody,
+}
+
+#[thin_delegate::fill_delegate]
+impl Hello for Focus {}
+
+// `len` is not covered for `Nobody`.
+#[thin_delegate::register]
+enum Layer {
+    Window(String),
+    #[thin_delegate::unit_variant(hello = "nobody".into())]
+    Nobody,
+}
+
+#[thin_delegate::fill_delegate]
+impl Hello for Layer {}
+
+fn main() {}
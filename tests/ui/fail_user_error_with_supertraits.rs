@@ -0,0 +1,56 @@
+use std::fmt::Write;
+
+#[thin_delegate::register]
+pub trait Named {
+    fn name(&self) -> String;
+}
+
+impl Named for String {
+    fn name(&self) -> String {
+        self.clone()
+    }
+}
+
+// `Write` is ambiguous as `thin_delegate::std_defs` has `fmt::Write` and `io::Write`.
+#[thin_delegate::register]
+pub trait Sink: Write + Named {
+    fn sink(&self);
+}
+
+#[thin_delegate::register]
+struct Hoge(String);
+
+#[thin_delegate::fill_delegate(with_supertraits = true)]
+impl Sink for Hoge {}
+
+#[thin_delegate::register]
+pub trait Greeter: Named {
+    fn greet(&self) -> String;
+}
+
+#[thin_delegate::register]
+struct Fuga(String);
+
+// Not a supertrait.
+#[thin_delegate::fill_delegate(with_supertraits(skip(Display)))]
+impl Greeter for Fuga {}
+
+#[thin_delegate::register]
+pub trait Duplicate: std::clone::Clone {
+    fn duplicate(&self) -> String;
+}
+
+#[thin_delegate::register]
+struct Piyo(String);
+
+// `Clone` in `std` is not bundled in `thin_delegate::std_defs`.
+#[thin_delegate::fill_delegate(with_supertraits(include(Clone)))]
+impl Duplicate for Piyo {}
+
+#[thin_delegate::register]
+struct Hogera(String);
+
+#[thin_delegate::fill_delegate(with_supertraits(skip(Named), include(Named)))]
+impl Greeter for Hogera {}
+
+fn main() {}
@@ -0,0 +1,113 @@
+// `#[thin_delegate::arm(Pattern)]` on a method gives only exceptional arms. Other variants are
+// delegated.
+
+#[thin_delegate::register]
+pub trait Hello {
+    fn hello(&self, prefix: &str) -> String;
+    fn rename(&mut self, name: &str);
+    fn into_name(self) -> String;
+}
+
+impl Hello for String {
+    fn hello(&self, prefix: &str) -> String {
+        format!("{prefix}, {self}")
+    }
+
+    fn rename(&mut self, name: &str) {
+        *self = name.to_string();
+    }
+
+    fn into_name(self) -> String {
+        self
+    }
+}
+
+impl Hello for char {
+    fn hello(&self, prefix: &str) -> String {
+        format!("{prefix}, {self}")
+    }
+
+    fn rename(&mut self, name: &str) {
+        *self = name.chars().next().unwrap();
+    }
+
+    fn into_name(self) -> String {
+        self.to_string()
+    }
+}
+
+#[thin_delegate::register]
+enum Hoge {
+    A(String),
+    B(char),
+    C(String),
+}
+
+#[thin_delegate::fill_delegate]
+impl Hello for Hoge {
+    #[thin_delegate::arm(Self::A(s))]
+    fn hello(&self, prefix: &str) -> String {
+        format!("{prefix}, {s}{s}")
+    }
+
+    #[thin_delegate::arm(Self::B('x') | Self::C(_))]
+    fn rename(&mut self, _name: &str) {
+        // Renaming is not allowed.
+        let _ = self;
+    }
+
+    #[thin_delegate::arm(Self::C(s))]
+    fn into_name(self) -> String {
+        s.to_uppercase()
+    }
+}
+
+#[thin_delegate::register]
+enum Fuga {
+    A(String),
+    B(String),
+    C(char),
+}
+
+// Overrides of a method may name the arguments differently.
+#[thin_delegate::fill_delegate]
+impl Hello for Fuga {
+    #[thin_delegate::arm(Self::A(_))]
+    fn hello(&self, _p: &str) -> String {
+        "a".to_string()
+    }
+
+    #[thin_delegate::arm(Self::B(s))]
+    fn hello(&self, prefix: &str) -> String {
+        format!("{prefix}; {s}")
+    }
+}
+
+fn main() {
+    let fuga = Fuga::A("fuga".to_string());
+    assert_eq!(fuga.hello("hello"), "a");
+    let fuga = Fuga::B("fuga".to_string());
+    assert_eq!(fuga.hello("hello"), "hello; fuga");
+    let mut fuga = Fuga::C('f');
+    assert_eq!(fuga.hello("hello"), "hello, f");
+    fuga.rename("g");
+    assert_eq!(fuga.into_name(), "g");
+
+    let mut a = Hoge::A("hoge".to_string());
+    assert_eq!(a.hello("hello"), "hello, hogehoge");
+    a.rename("fuga");
+    assert_eq!(a.into_name(), "fuga");
+
+    let mut b = Hoge::B('h');
+    assert_eq!(b.hello("hello"), "hello, h");
+    b.rename("y");
+    assert_eq!(b.hello("hello"), "hello, y");
+    let mut b = Hoge::B('x');
+    b.rename("y");
+    assert_eq!(b.hello("hello"), "hello, x");
+
+    let mut c = Hoge::C("piyo".to_string());
+    c.rename("fuga");
+    assert_eq!(c.hello("hello"), "hello, piyo");
+    assert_eq!(c.into_name(), "PIYO");
+}
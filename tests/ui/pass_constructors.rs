@@ -0,0 +1,139 @@
+use std::marker::PhantomData;
+
+pub struct Config {
+    name: String,
+}
+
+#[thin_delegate::register]
+pub trait Surface {
+    fn new(cfg: Config) -> Self;
+    fn with_default() -> Self
+    where
+        Self: Sized;
+    fn name(&self) -> String;
+}
+
+pub struct WlSurface {
+    name: String,
+}
+
+impl Surface for WlSurface {
+    fn new(cfg: Config) -> Self {
+        WlSurface { name: cfg.name }
+    }
+
+    fn with_default() -> Self {
+        WlSurface {
+            name: "default".to_string(),
+        }
+    }
+
+    fn name(&self) -> String {
+        self.name.clone()
+    }
+}
+
+pub struct X11Surface {
+    id: u32,
+}
+
+impl Surface for X11Surface {
+    fn new(cfg: Config) -> Self {
+        X11Surface {
+            id: cfg.name.len() as u32,
+        }
+    }
+
+    fn with_default() -> Self {
+        X11Surface { id: 0 }
+    }
+
+    fn name(&self) -> String {
+        format!("x11 #{}", self.id)
+    }
+}
+
+// Single-field struct.
+#[thin_delegate::register]
+pub struct XdgShellSurface(WlSurface);
+
+#[thin_delegate::fill_delegate]
+impl Surface for XdgShellSurface {}
+
+// Marker fields are filled with `Default::default()`.
+#[thin_delegate::register]
+pub struct Tagged<T> {
+    surface: WlSurface,
+    marker: PhantomData<T>,
+}
+
+#[thin_delegate::fill_delegate]
+impl<T> Surface for Tagged<T> {}
+
+// Enum constructs the variant marked with `#[thin_delegate::constructor_variant]`.
+#[thin_delegate::register]
+pub enum Backend {
+    Wl(WlSurface),
+    #[thin_delegate::constructor_variant]
+    X11(X11Surface),
+}
+
+#[thin_delegate::fill_delegate]
+impl Surface for Backend {}
+
+// Or `constructor_variant` gives the variant.
+#[thin_delegate::register]
+#[derive(Default)]
+pub enum AnySurface {
+    Wl(WlSurface),
+    X11 {
+        id: u32,
+        #[thin_delegate::delegate]
+        surface: X11Surface,
+    },
+    #[default]
+    Empty,
+}
+
+#[thin_delegate::fill_delegate(constructor_variant = Wl, unit_variants = panic)]
+impl Surface for AnySurface {}
+
+#[thin_delegate::register]
+pub trait Open {
+    fn open(name: &str) -> Self;
+}
+
+impl Open for WlSurface {
+    fn open(name: &str) -> Self {
+        WlSurface {
+            name: name.to_string(),
+        }
+    }
+}
+
+fn into_session(surface: WlSurface) -> WlSurface {
+    WlSurface {
+        name: format!("session of {}", surface.name),
+    }
+}
+
+// `map_arg` and `map_return` are applied to the call to construct the field.
+#[thin_delegate::register]
+pub struct Session(WlSurface);
+
+#[thin_delegate::fill_delegate(map_arg(name = str::trim), map_return = into_session)]
+impl Open for Session {}
+
+fn main() {
+    let cfg = || Config {
+        name: "alice".to_string(),
+    };
+
+    assert_eq!(XdgShellSurface::new(cfg()).name(), "alice");
+    assert_eq!(XdgShellSurface::with_default().name(), "default");
+    assert_eq!(Tagged::<u8>::new(cfg()).name(), "alice");
+    assert!(matches!(AnySurface::new(cfg()), AnySurface::Wl(_)));
+    assert!(matches!(AnySurface::default(), AnySurface::Empty));
+    assert_eq!(Backend::new(cfg()).name(), "x11 #5");
+    assert_eq!(Session::open(" bob ").0.name, "session of bob");
+}
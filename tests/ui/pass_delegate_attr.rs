@@ -0,0 +1,97 @@
+// `#[thin_delegate::delegate]` marks the field to delegate to in structs and enum variants with
+// multiple fields. `#[thin_delegate::delegate(Trait, ...)]` marks it only for the traits.
+
+#[thin_delegate::register]
+pub trait Hello {
+    fn hello_ref(&self) -> String;
+    fn hello_ref_mut(&mut self) -> String;
+    fn hello_consume(self) -> String;
+}
+
+#[thin_delegate::register]
+pub trait Bye {
+    fn bye(&self) -> String;
+}
+
+impl Hello for String {
+    fn hello_ref(&self) -> String {
+        format!("hello, {self}")
+    }
+
+    fn hello_ref_mut(&mut self) -> String {
+        self.push('!');
+        format!("hello, {self}")
+    }
+
+    fn hello_consume(self) -> String {
+        format!("hello, {self}")
+    }
+}
+
+impl Bye for char {
+    fn bye(&self) -> String {
+        format!("bye, {self}")
+    }
+}
+
+#[thin_delegate::register]
+struct Named {
+    id: usize,
+    #[thin_delegate::delegate]
+    name: String,
+}
+
+#[thin_delegate::fill_delegate]
+impl Hello for Named {}
+
+#[thin_delegate::register]
+struct Unnamed(
+    #[thin_delegate::delegate(Hello)] String,
+    #[thin_delegate::delegate(Bye)] char,
+);
+
+#[thin_delegate::fill_delegate]
+impl Hello for Unnamed {}
+
+#[thin_delegate::fill_delegate]
+impl Bye for Unnamed {}
+
+#[thin_delegate::register]
+enum Animal {
+    Cat {
+        id: usize,
+        #[thin_delegate::delegate]
+        name: String,
+    },
+    Dog(usize, #[thin_delegate::delegate] String),
+    Bird(String),
+}
+
+#[thin_delegate::fill_delegate]
+impl Hello for Animal {}
+
+fn main() {
+    let mut named = Named {
+        id: 1,
+        name: "Alice".to_string(),
+    };
+    assert_eq!(named.id, 1);
+    assert_eq!(named.hello_ref(), "hello, Alice");
+    assert_eq!(named.hello_ref_mut(), "hello, Alice!");
+    assert_eq!(named.hello_consume(), "hello, Alice!");
+
+    let unnamed = Unnamed("Bob".to_string(), 'B');
+    assert_eq!(unnamed.hello_ref(), "hello, Bob");
+    assert_eq!(unnamed.bye(), "bye, B");
+
+    let mut cat = Animal::Cat {
+        id: 1,
+        name: "Tama".to_string(),
+    };
+    assert_eq!(cat.hello_ref_mut(), "hello, Tama!");
+    assert_eq!(cat.hello_consume(), "hello, Tama!");
+    let dog = Animal::Dog(2, "Pochi".to_string());
+    assert_eq!(dog.hello_ref(), "hello, Pochi");
+    let bird = Animal::Bird("Piyo".to_string());
+    assert_eq!(bird.hello_consume(), "hello, Piyo");
+}
@@ -0,0 +1,68 @@
+// Structs/enums in external crates can be used if their fields are public.
+
+// Assume that this module is an external crate.
+mod upstream {
+    pub enum Either<L, R> {
+        Left(L),
+        Right(R),
+    }
+
+    pub struct Wrapper {
+        pub inner: String,
+    }
+}
+
+#[thin_delegate::external_structenum_def]
+mod __external_structenum_def {
+    #[thin_delegate::register]
+    pub enum Either<L, R> {
+        Left(L),
+        Right(R),
+    }
+}
+
+mod copies {
+    // The generated impl uses the real path, so you don't need to import the struct.
+    #[thin_delegate::external_structenum_def(path = crate::upstream::Wrapper)]
+    #[thin_delegate::register]
+    pub struct Wrapper {
+        pub inner: String,
+    }
+}
+
+#[thin_delegate::register]
+trait Hello {
+    fn hello(&self) -> String;
+}
+
+impl Hello for String {
+    fn hello(&self) -> String {
+        format!("hello, {self}")
+    }
+}
+
+impl Hello for char {
+    fn hello(&self) -> String {
+        format!("hello, {self}")
+    }
+}
+
+use upstream::Either;
+
+#[thin_delegate::fill_delegate(external_structenum_def = __external_structenum_def)]
+impl Hello for Either<String, char> {}
+
+#[thin_delegate::fill_delegate(external_structenum_def = copies)]
+impl Hello for Wrapper {}
+
+fn main() {
+    let left = Either::<String, char>::Left("Alice".to_string());
+    let right = Either::<String, char>::Right('B');
+    assert_eq!(left.hello(), "hello, Alice");
+    assert_eq!(right.hello(), "hello, B");
+
+    let wrapper = upstream::Wrapper {
+        inner: "Carol".to_string(),
+    };
+    assert_eq!(wrapper.hello(), "hello, Carol");
+}
@@ -0,0 +1,127 @@
+// `from_file` imports traits and the `use`s they need from a Rust source file.
+//
+// The path is relative to `CARGO_MANIFEST_DIR`, which is `target/tests/trybuild/thin_delegate` in
+// UI tests.
+
+use std::collections::BTreeMap;
+
+// Assume that this module is the crate `upstream`, and `tests/ui/vendor/upstream/src/hello.rs` is a
+// copy of `upstream::hello`.
+mod upstream {
+    pub struct Name(pub String);
+
+    pub mod hello {
+        use crate::upstream::Name;
+        use std::collections::{BTreeMap, HashMap};
+
+        pub trait Hello {
+            fn hello(&self, name: &Name) -> String;
+
+            fn bye(&self) -> String {
+                "bye".to_string()
+            }
+        }
+
+        pub struct Event(pub u32);
+
+        pub trait Handler {
+            fn handle(&self, event: &Event) -> u32;
+        }
+
+        pub trait Registry {
+            fn names(&self) -> HashMap<u32, Name>;
+        }
+
+        pub mod greeting {
+            use super::*;
+
+            pub enum Mood {
+                Happy,
+                Sleepy,
+            }
+
+            pub trait Greeting {
+                fn greet(&self, name: &Name) -> String;
+
+                fn greetings(&self) -> BTreeMap<u32, String>;
+
+                fn mood(&self) -> Mood;
+            }
+        }
+    }
+}
+
+#[thin_delegate::external_trait_def(
+    from_file = "../../../../tests/ui/vendor/upstream/src/hello.rs",
+    traits(Hello, Handler, Registry, Greeting),
+    crate_path = crate::upstream,
+    with_uses = true,
+)]
+mod __external_trait_def {}
+
+impl upstream::hello::Hello for String {
+    fn hello(&self, name: &upstream::Name) -> String {
+        format!("{self}, {}", name.0)
+    }
+}
+
+impl upstream::hello::Handler for String {
+    fn handle(&self, event: &upstream::hello::Event) -> u32 {
+        self.len() as u32 + event.0
+    }
+}
+
+impl upstream::hello::Registry for String {
+    fn names(&self) -> std::collections::HashMap<u32, upstream::Name> {
+        [(0, upstream::Name(self.clone()))].into_iter().collect()
+    }
+}
+
+impl upstream::hello::greeting::Greeting for String {
+    fn greet(&self, name: &upstream::Name) -> String {
+        format!("{self}, {}!", name.0)
+    }
+
+    fn greetings(&self) -> BTreeMap<u32, String> {
+        [(0, self.clone())].into_iter().collect()
+    }
+
+    fn mood(&self) -> upstream::hello::greeting::Mood {
+        if self.is_empty() {
+            upstream::hello::greeting::Mood::Sleepy
+        } else {
+            upstream::hello::greeting::Mood::Happy
+        }
+    }
+}
+
+#[thin_delegate::register]
+struct Hoge(String);
+
+#[thin_delegate::fill_delegate(external_trait_def = __external_trait_def)]
+impl upstream::hello::Hello for Hoge {}
+
+#[thin_delegate::fill_delegate(external_trait_def = __external_trait_def)]
+impl upstream::hello::Handler for Hoge {}
+
+#[thin_delegate::fill_delegate(external_trait_def = __external_trait_def)]
+impl upstream::hello::Registry for Hoge {}
+
+#[thin_delegate::fill_delegate(external_trait_def = __external_trait_def)]
+impl upstream::hello::greeting::Greeting for Hoge {}
+
+fn main() {
+    use upstream::hello::greeting::Greeting;
+    use upstream::hello::greeting::Mood;
+    use upstream::hello::{Event, Handler, Hello, Registry};
+
+    let hoge = Hoge("hello".to_string());
+    let alice = upstream::Name("Alice".to_string());
+    assert_eq!(hoge.hello(&alice), "hello, Alice");
+    assert_eq!(hoge.bye(), "bye");
+    assert_eq!(hoge.names()[&0].0, "hello");
+    assert_eq!(hoge.greet(&alice), "hello, Alice!");
+    assert_eq!(hoge.greetings()[&0], "hello");
+    assert_eq!(hoge.handle(&Event(1)), 6);
+    assert!(matches!(hoge.mood(), Mood::Happy));
+}
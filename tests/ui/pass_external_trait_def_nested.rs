@@ -0,0 +1,87 @@
+// `#[thin_delegate::external_trait_def]` processes nested modules, and `with_uses` also carries
+// type aliases and consts.
+
+mod external {
+    pub mod input {
+        pub struct Event;
+
+        pub mod keyboard {
+            pub type Keycode = u32;
+            pub const NUM_KEYS: usize = 4;
+
+            pub trait KeyboardTarget {
+                fn key(&self, event: &super::Event, code: Keycode) -> String;
+                fn keys(&self) -> [Keycode; NUM_KEYS];
+            }
+
+            impl KeyboardTarget for String {
+                fn key(&self, _event: &super::Event, code: Keycode) -> String {
+                    format!("{self}: {code}")
+                }
+
+                fn keys(&self) -> [Keycode; NUM_KEYS] {
+                    [1, 2, 3, 4]
+                }
+            }
+        }
+
+        pub mod pointer {
+            pub trait PointerTarget {
+                fn button(&self, event: &super::Event) -> String;
+            }
+
+            impl PointerTarget for String {
+                fn button(&self, _event: &super::Event) -> String {
+                    format!("{self}: button")
+                }
+            }
+        }
+    }
+}
+
+#[thin_delegate::external_trait_def(with_uses = true, verify = crate::external)]
+mod __external_trait_def {
+    pub(crate) mod input {
+        pub(crate) mod keyboard {
+            use crate::external::input::Event;
+
+            type Keycode = u32;
+            const NUM_KEYS: usize = 4;
+
+            #[thin_delegate::register]
+            pub trait KeyboardTarget {
+                fn key(&self, event: &Event, code: Keycode) -> String;
+                fn keys(&self) -> [Keycode; NUM_KEYS];
+            }
+        }
+
+        pub(crate) mod pointer {
+            use crate::external::input::Event;
+
+            #[thin_delegate::register]
+            pub trait PointerTarget {
+                fn button(&self, event: &Event) -> String;
+            }
+        }
+    }
+}
+
+#[thin_delegate::register]
+struct Hoge(String);
+
+#[thin_delegate::fill_delegate(external_trait_def = __external_trait_def::input::keyboard)]
+impl external::input::keyboard::KeyboardTarget for Hoge {}
+
+#[thin_delegate::fill_delegate(external_trait_def = __external_trait_def::input::pointer)]
+impl external::input::pointer::PointerTarget for Hoge {}
+
+fn main() {
+    use external::input::keyboard::KeyboardTarget;
+    use external::input::pointer::PointerTarget;
+    use external::input::Event;
+
+    let hoge = Hoge("hoge".to_string());
+    assert_eq!(hoge.key(&Event, 42), "hoge: 42");
+    assert_eq!(hoge.keys(), [1, 2, 3, 4]);
+    assert_eq!(hoge.button(&Event), "hoge: button");
+}
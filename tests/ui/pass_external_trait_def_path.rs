@@ -0,0 +1,36 @@
+// `#[thin_delegate::external_trait_def(path = ...)]` can be placed on a copied trait directly.
+// The generated impl uses the real path, so you don't need to import the trait.
+
+mod copies {
+    #[thin_delegate::external_trait_def(path = ::std::io::Write)]
+    #[thin_delegate::register]
+    pub trait Write {
+        fn write(&mut self, buf: &[u8]) -> ::std::io::Result<usize>;
+
+        fn flush(&mut self) -> ::std::io::Result<()>;
+    }
+
+    #[thin_delegate::external_trait_def(path = ::core::convert::AsRef)]
+    #[thin_delegate::register]
+    pub trait AsRef<T: ?Sized> {
+        fn as_ref(&self) -> &T;
+    }
+}
+
+#[thin_delegate::register]
+struct Buffer(Vec<u8>);
+
+#[thin_delegate::fill_delegate(external_trait_def = copies)]
+impl Write for Buffer {}
+
+#[thin_delegate::fill_delegate(external_trait_def = copies)]
+impl AsRef<[u8]> for Buffer {}
+
+fn main() {
+    use std::io::Write as _;
+
+    let mut buffer = Buffer(vec![]);
+    buffer.write_all(b"hello").unwrap();
+    buffer.flush().unwrap();
+    assert_eq!(buffer.as_ref(), b"hello");
+}
@@ -0,0 +1,141 @@
+// `verify` checks that the copies match with the real traits at compile time.
+// Compare with fail_user_error_external_trait_def_verify.rs
+
+mod external {
+    pub struct Arg;
+
+    pub trait Hello<'a, T: Clone> {
+        type Output: Clone;
+
+        const NAME: &'static str;
+
+        fn hello(&self, arg: &'a Arg, t: T) -> Self::Output;
+
+        fn hello_twice(&self, arg: &'a Arg, t: T) -> (Self::Output, Self::Output) {
+            (self.hello(arg, t.clone()), self.hello(arg, t))
+        }
+    }
+
+    pub trait Bye: Clone + std::fmt::Debug {
+        fn bye(&self) -> String;
+
+        // The copy may omit methods with default implementation.
+        fn bye_twice(&self) -> String {
+            format!("{}{}", self.bye(), self.bye())
+        }
+    }
+
+    pub unsafe trait Danger {
+        fn danger(&self) -> usize;
+    }
+
+    pub trait Items {
+        fn items(&self) -> impl Iterator<Item = u32>;
+
+        fn refs(&self) -> impl Iterator<Item = &u32> + '_;
+    }
+
+    unsafe impl Danger for String {
+        fn danger(&self) -> usize {
+            self.len()
+        }
+    }
+
+    impl Items for Vec<u32> {
+        fn items(&self) -> impl Iterator<Item = u32> {
+            self.clone().into_iter()
+        }
+
+        fn refs(&self) -> impl Iterator<Item = &u32> + '_ {
+            self.iter()
+        }
+    }
+
+    impl<'a> Hello<'a, u8> for String {
+        type Output = String;
+
+        const NAME: &'static str = "String";
+
+        fn hello(&self, _arg: &'a Arg, t: u8) -> Self::Output {
+            format!("hello, {self}{t}")
+        }
+    }
+
+    impl Bye for String {
+        fn bye(&self) -> String {
+            format!("bye, {self}")
+        }
+    }
+}
+
+#[thin_delegate::external_trait_def(with_uses = true, verify = crate::external)]
+mod __external_trait_def {
+    use crate::external::Arg;
+
+    #[thin_delegate::register]
+    pub trait Hello<'a, T: Clone> {
+        type Output: Clone;
+
+        const NAME: &'static str;
+
+        fn hello(&self, arg: &'a Arg, t: T) -> Self::Output;
+
+        fn hello_twice(&self, arg: &'a Arg, t: T) -> (Self::Output, Self::Output) {
+            (self.hello(arg, t.clone()), self.hello(arg, t))
+        }
+    }
+
+    #[thin_delegate::register]
+    pub trait Bye: Clone + std::fmt::Debug {
+        fn bye(&self) -> String;
+    }
+
+    #[thin_delegate::register]
+    pub unsafe trait Danger {
+        fn danger(&self) -> usize;
+    }
+
+    // Methods returning `impl Trait` are also verified.
+    #[thin_delegate::register]
+    pub trait Items {
+        fn items(&self) -> impl Iterator<Item = u32>;
+
+        fn refs(&self) -> impl Iterator<Item = &u32> + '_;
+    }
+}
+
+#[thin_delegate::register]
+#[derive(Clone, Debug)]
+struct Hoge(String);
+
+#[thin_delegate::fill_delegate(external_trait_def = __external_trait_def)]
+impl<'a> external::Hello<'a, u8> for Hoge {
+    type Output = String;
+
+    const NAME: &'static str = "Hoge";
+}
+
+#[thin_delegate::fill_delegate(external_trait_def = __external_trait_def)]
+impl external::Bye for Hoge {}
+
+#[thin_delegate::fill_delegate(external_trait_def = __external_trait_def)]
+unsafe impl external::Danger for Hoge {}
+
+#[thin_delegate::register]
+struct Fuga(Vec<u32>);
+
+#[thin_delegate::fill_delegate(external_trait_def = __external_trait_def)]
+impl external::Items for Fuga {}
+
+fn main() {
+    use external::{Arg, Bye, Danger, Hello, Items};
+
+    let hoge = Hoge("world".to_string());
+    assert_eq!(hoge.hello(&Arg, 1), "hello, world1");
+    assert_eq!(hoge.bye(), "bye, world");
+    assert_eq!(hoge.danger(), 5);
+
+    let fuga = Fuga(vec![1, 2]);
+    assert_eq!(fuga.items().collect::<Vec<_>>(), vec![1, 2]);
+    assert_eq!(fuga.refs().sum::<u32>(), 3);
+}
@@ -0,0 +1,102 @@
+#[thin_delegate::register]
+pub trait Focus {
+    fn focus_id(&self) -> u32;
+    fn is_focused(&self) -> bool;
+}
+
+#[thin_delegate::register]
+pub trait Resize {
+    fn resize(&mut self, width: u32, height: u32) -> (u32, u32);
+}
+
+pub struct WlSurface {
+    id: u32,
+    size: (u32, u32),
+}
+
+impl Focus for WlSurface {
+    fn focus_id(&self) -> u32 {
+        self.id
+    }
+
+    fn is_focused(&self) -> bool {
+        self.id % 2 == 0
+    }
+}
+
+impl Resize for WlSurface {
+    fn resize(&mut self, width: u32, height: u32) -> (u32, u32) {
+        self.size = (width, height);
+        self.size
+    }
+}
+
+#[thin_delegate::register]
+pub struct XdgShellFocus(WlSurface);
+
+#[thin_delegate::register]
+pub struct LayerShellFocus(WlSurface);
+
+#[thin_delegate::register]
+pub struct PopupFocus {
+    surface: WlSurface,
+    parent: u32,
+}
+
+#[thin_delegate::register]
+pub struct Tagged<T> {
+    tag: &'static str,
+    #[thin_delegate::delegate]
+    inner: T,
+}
+
+#[thin_delegate::register]
+pub enum AnyFocus {
+    Xdg(XdgShellFocus),
+    Layer(LayerShellFocus),
+}
+
+thin_delegate::fill_delegate_for! {
+    impl Focus for XdgShellFocus, LayerShellFocus, AnyFocus
+}
+
+// Targets can have their own generic parameters, and a trailing comma is allowed.
+thin_delegate::fill_delegate_for! {
+    impl Focus for <T: Focus> Tagged<T>,
+}
+
+// Arguments of `fill_delegate` are common to all targets.
+thin_delegate::fill_delegate_for! {
+    #[fill_delegate(scheme = |f| f(&mut self.0))]
+    impl Resize for XdgShellFocus, LayerShellFocus
+}
+
+thin_delegate::fill_delegate_for! {
+    #[thin_delegate::fill_delegate(scheme = |f| f(&self.surface))]
+    impl Focus for PopupFocus;
+}
+
+fn main() {
+    let surface = |id| WlSurface { id, size: (0, 0) };
+
+    assert_eq!(XdgShellFocus(surface(1)).focus_id(), 1);
+    assert!(LayerShellFocus(surface(2)).is_focused());
+    assert_eq!(AnyFocus::Layer(LayerShellFocus(surface(3))).focus_id(), 3);
+    let tagged = Tagged {
+        tag: "xdg",
+        inner: XdgShellFocus(surface(4)),
+    };
+    assert_eq!(tagged.tag, "xdg");
+    assert_eq!(tagged.focus_id(), 4);
+    let popup = PopupFocus {
+        surface: surface(5),
+        parent: 4,
+    };
+    assert_eq!(popup.parent, 4);
+    assert_eq!(popup.focus_id(), 5);
+
+    let mut xdg = XdgShellFocus(surface(6));
+    assert_eq!(xdg.resize(640, 480), (640, 480));
+    assert_eq!(xdg.0.size, (640, 480));
+    assert_eq!(LayerShellFocus(surface(7)).resize(1, 2), (1, 2));
+}
@@ -0,0 +1,139 @@
+mod renderer {
+    pub trait Renderer {
+        fn name(&self) -> String;
+    }
+
+    pub struct Gles;
+
+    impl Renderer for Gles {
+        fn name(&self) -> String {
+            "gles".to_string()
+        }
+    }
+}
+
+use renderer::Renderer;
+
+#[thin_delegate::register]
+pub trait Element {
+    fn id(&self) -> usize;
+    fn geometry(&self) -> (u32, u32);
+}
+
+#[thin_delegate::register]
+pub trait RenderElement<R: Renderer> {
+    fn draw(&self, renderer: &R) -> String;
+}
+
+#[thin_delegate::register]
+pub trait IsAlive {
+    fn alive(&self) -> bool;
+}
+
+pub struct Surface {
+    id: usize,
+}
+
+impl Element for Surface {
+    fn id(&self) -> usize {
+        self.id
+    }
+
+    fn geometry(&self) -> (u32, u32) {
+        (1, 2)
+    }
+}
+
+impl<R: Renderer> RenderElement<R> for Surface {
+    fn draw(&self, renderer: &R) -> String {
+        format!("surface {} by {}", self.id, renderer.name())
+    }
+}
+
+impl IsAlive for Surface {
+    fn alive(&self) -> bool {
+        true
+    }
+}
+
+pub struct Texture;
+
+impl Element for Texture {
+    fn id(&self) -> usize {
+        0
+    }
+
+    fn geometry(&self) -> (u32, u32) {
+        (3, 4)
+    }
+}
+
+impl<R: Renderer> RenderElement<R> for Texture {
+    fn draw(&self, renderer: &R) -> String {
+        format!("texture by {}", renderer.name())
+    }
+}
+
+impl IsAlive for Texture {
+    fn alive(&self) -> bool {
+        false
+    }
+}
+
+#[thin_delegate::register]
+#[thin_delegate::fill_delegates(
+    traits(
+        Element,
+        impl<R: Renderer> RenderElement<R>,
+        IsAlive { unit_variants = default },
+    ),
+    unit_variants = panic,
+)]
+pub enum Elem {
+    Surface(Surface),
+    Texture(Texture),
+    Empty,
+}
+
+// Order of attributes doesn't matter, and common arguments are optional.
+#[thin_delegate::fill_delegates(traits(impl<T: Element> Element, impl<T: IsAlive> IsAlive))]
+#[thin_delegate::register]
+pub struct Wrapper<T>(T);
+
+#[thin_delegate::register]
+#[thin_delegate::fill_delegates(traits(Element, IsAlive), scheme = |f| f(&self.surface))]
+pub struct Named {
+    name: String,
+    surface: Surface,
+}
+
+fn main() {
+    let elem = Elem::Surface(Surface { id: 1 });
+    assert_eq!(elem.id(), 1);
+    assert_eq!(elem.geometry(), (1, 2));
+    assert_eq!(elem.draw(&renderer::Gles), "surface 1 by gles");
+    assert!(elem.alive());
+
+    let elem = Elem::Texture(Texture);
+    assert_eq!(elem.draw(&renderer::Gles), "texture by gles");
+    assert!(!elem.alive());
+
+    let elem = Elem::Empty;
+    assert!(!elem.alive());
+    // Silence the message of the panic.
+    std::panic::set_hook(Box::new(|_| {}));
+    let result = std::panic::catch_unwind(|| Elem::Empty.id());
+    let _ = std::panic::take_hook();
+    assert!(result.is_err());
+
+    let wrapper = Wrapper(Surface { id: 2 });
+    assert_eq!(wrapper.id(), 2);
+    assert!(wrapper.alive());
+
+    let named = Named {
+        name: "a".to_string(),
+        surface: Surface { id: 3 },
+    };
+    assert_eq!(named.name, "a");
+    assert_eq!(named.id(), 3);
+}
@@ -0,0 +1,125 @@
+use std::rc::Rc;
+use std::sync::Arc;
+
+// Methods that can't be forwarded are left to their default implementations by `keep_default_impl`.
+#[thin_delegate::register(forward_impls(ref, ref_mut, box, rc, arc), keep_default_impl(grow, describe))]
+pub trait Shape {
+    type Unit;
+    const SIDES: usize;
+
+    fn name(&self) -> String;
+    fn area(&self, scale: f64) -> f64;
+    fn unit(&self) -> Self::Unit;
+
+    // `&mut self` is forwarded only through `&mut T` and `Box<T>`.
+    fn grow(&mut self, by: f64) {
+        let _ = by;
+    }
+
+    // `#[cfg]` is kept in forwarded methods.
+    #[cfg(any())]
+    fn never(&self) -> usize;
+
+    // Methods with `where Self: Sized` can't be forwarded.
+    fn describe(self) -> String
+    where
+        Self: Sized,
+    {
+        format!("{} with {} sides", self.name(), Self::SIDES)
+    }
+}
+
+pub struct Square(f64);
+
+impl Shape for Square {
+    type Unit = &'static str;
+    const SIDES: usize = 4;
+
+    fn name(&self) -> String {
+        "square".to_string()
+    }
+
+    fn area(&self, scale: f64) -> f64 {
+        self.0 * self.0 * scale
+    }
+
+    fn unit(&self) -> Self::Unit {
+        "cm"
+    }
+
+    fn grow(&mut self, by: f64) {
+        self.0 += by;
+    }
+}
+
+#[thin_delegate::register(forward_impls(ref, box))]
+pub trait Container<T> {
+    fn get(&self, index: usize) -> Option<&T>;
+}
+
+impl<T> Container<T> for Vec<T> {
+    fn get(&self, index: usize) -> Option<&T> {
+        self.as_slice().get(index)
+    }
+}
+
+#[thin_delegate::register(forward_impls(ref))]
+pub trait Named {
+    fn name(&self) -> String;
+}
+
+// Impls of supertraits are required.
+#[thin_delegate::register(forward_impls(ref))]
+pub trait Greeter: Named {
+    fn greet(&self) -> String;
+}
+
+pub struct Alice;
+
+impl Named for Alice {
+    fn name(&self) -> String {
+        "Alice".to_string()
+    }
+}
+
+impl Greeter for Alice {
+    fn greet(&self) -> String {
+        format!("hello, {}", self.name())
+    }
+}
+
+fn total_area<S: Shape + ?Sized>(shapes: &[&S]) -> f64 {
+    shapes.iter().map(|shape| shape.area(1.0)).sum()
+}
+
+fn main() {
+    let square = Square(2.0);
+    assert_eq!((&square).name(), "square");
+    assert_eq!(<&Square as Shape>::SIDES, 4);
+    assert_eq!((&square).unit(), "cm");
+    assert_eq!((&square).describe(), "square with 4 sides");
+
+    let mut square = Square(2.0);
+    {
+        let mut r = &mut square;
+        Shape::grow(&mut r, 1.0);
+    }
+    assert_eq!(square.area(1.0), 9.0);
+
+    let mut boxed = Box::new(Square(1.0));
+    boxed.grow(1.0);
+    assert_eq!(boxed.area(2.0), 8.0);
+    assert_eq!(<Box<Square> as Shape>::SIDES, 4);
+
+    // `grow()` through `Rc<T>` is the default implementation, which does nothing.
+    let mut rc = Rc::new(Square(3.0));
+    Shape::grow(&mut rc, 1.0);
+    assert_eq!(rc.area(1.0), 9.0);
+    assert_eq!(Arc::new(Square(1.0)).name(), "square");
+
+    assert_eq!(total_area(&[&Square(1.0), &Square(2.0)]), 5.0);
+    assert_eq!(Greeter::greet(&&Alice), "hello, Alice");
+    assert_eq!(Container::get(&&vec![1, 2], 1), Some(&2));
+    let boxed: Box<dyn Container<i32>> = Box::new(vec![1, 2]);
+    assert_eq!(Container::get(&boxed, 0), Some(&1));
+}
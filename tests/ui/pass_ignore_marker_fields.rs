@@ -0,0 +1,87 @@
+// Fields of `PhantomData<..>`, `PhantomPinned` and `()` are ignored when choosing the field to
+// delegate to, unless it is the only field.
+
+use std::marker::{PhantomData, PhantomPinned};
+
+#[thin_delegate::register]
+pub trait Hello {
+    fn hello(&self) -> String;
+    fn hello_consume(self) -> String;
+}
+
+impl Hello for String {
+    fn hello(&self) -> String {
+        format!("hello, {self}")
+    }
+
+    fn hello_consume(self) -> String {
+        format!("hello, {self}")
+    }
+}
+
+impl<T> Hello for PhantomData<T> {
+    fn hello(&self) -> String {
+        "hello, phantom".to_string()
+    }
+
+    fn hello_consume(self) -> String {
+        "hello, phantom".to_string()
+    }
+}
+
+#[thin_delegate::register]
+struct Handle<T>(String, PhantomData<T>);
+
+#[thin_delegate::fill_delegate]
+impl<T> Hello for Handle<T> {}
+
+#[thin_delegate::register]
+struct Pinned {
+    _pin: PhantomPinned,
+    name: String,
+    _unit: (),
+}
+
+#[thin_delegate::fill_delegate]
+impl Hello for Pinned {}
+
+#[thin_delegate::register]
+enum Tagged<T> {
+    Named {
+        inner: String,
+        _marker: PhantomData<T>,
+    },
+    Unnamed(std::marker::PhantomData<T>, String),
+}
+
+#[thin_delegate::fill_delegate]
+impl<T> Hello for Tagged<T> {}
+
+#[thin_delegate::register]
+struct Tag<T>(PhantomData<T>);
+
+#[thin_delegate::fill_delegate]
+impl<T> Hello for Tag<T> {}
+
+fn main() {
+    let handle = Handle::<u8>("Alice".to_string(), PhantomData);
+    assert_eq!(handle.hello(), "hello, Alice");
+
+    let pinned = Pinned {
+        _pin: PhantomPinned,
+        name: "Bob".to_string(),
+        _unit: (),
+    };
+    assert_eq!(pinned.hello_consume(), "hello, Bob");
+
+    let named = Tagged::<u8>::Named {
+        inner: "Carol".to_string(),
+        _marker: PhantomData,
+    };
+    assert_eq!(named.hello(), "hello, Carol");
+    let unnamed = Tagged::<u8>::Unnamed(PhantomData, "Dave".to_string());
+    assert_eq!(unnamed.hello_consume(), "hello, Dave");
+
+    let tag = Tag::<u8>(PhantomData);
+    assert_eq!(tag.hello(), "hello, phantom");
+}
@@ -0,0 +1,136 @@
+use std::marker::PhantomData;
+
+#[thin_delegate::register]
+pub trait Hello {
+    fn hello(&self) -> String;
+}
+
+#[thin_delegate::register]
+pub trait Convert<T> {
+    fn convert(&self) -> T;
+}
+
+impl Hello for String {
+    fn hello(&self) -> String {
+        format!("hello, {self}")
+    }
+}
+
+impl<T: Hello + ?Sized> Hello for Box<T> {
+    fn hello(&self) -> String {
+        (**self).hello()
+    }
+}
+
+impl Convert<usize> for String {
+    fn convert(&self) -> usize {
+        self.len()
+    }
+}
+
+mod legacy {
+    #[thin_delegate::register]
+    pub trait Greet {
+        fn hello(&self) -> String;
+    }
+
+    impl Greet for u32 {
+        fn hello(&self) -> String {
+            format!("hello, #{self}")
+        }
+    }
+}
+
+#[thin_delegate::register]
+pub struct Wrapper<T>(T);
+
+// `where T: Hello` is inferred. Names of parameters can differ from the definition.
+#[thin_delegate::fill_delegate(infer_bounds = true)]
+impl<U> Hello for Wrapper<U> {}
+
+// `where T: Convert<R>`.
+#[thin_delegate::fill_delegate(infer_bounds = true)]
+impl<T, R> Convert<R> for Wrapper<T> {}
+
+#[thin_delegate::register]
+pub struct Tagged<T, M> {
+    #[thin_delegate::delegate]
+    inner: Box<T>,
+    marker: PhantomData<M>,
+}
+
+// `where Box<T>: Hello`. `M` is not bounded.
+#[thin_delegate::fill_delegate(infer_bounds = true)]
+impl<T, M> Hello for Tagged<T, M> {}
+
+#[thin_delegate::register]
+pub enum Either<L, R> {
+    Left(L),
+    Right(R),
+    Fixed(String),
+}
+
+// `where L: Hello, R: Hello`. `String` doesn't mention parameters.
+#[thin_delegate::fill_delegate(infer_bounds = true)]
+impl<L, R> Hello for Either<L, R> {}
+
+#[thin_delegate::register]
+pub struct Legacy<T>(T);
+
+// `where T: legacy::Greet` for `to_trait`.
+#[thin_delegate::fill_delegate(to_trait = legacy::Greet, infer_bounds = true)]
+impl<T> Hello for Legacy<T> {}
+
+#[thin_delegate::register]
+pub trait Named {
+    fn name(&self) -> String;
+}
+
+#[thin_delegate::register]
+pub trait Greeter: Named {
+    fn greet(&self) -> String;
+}
+
+impl Named for String {
+    fn name(&self) -> String {
+        self.clone()
+    }
+}
+
+impl Greeter for String {
+    fn greet(&self) -> String {
+        format!("hi, {self}")
+    }
+}
+
+#[thin_delegate::register]
+pub struct Friend<T>(T);
+
+// `infer_bounds` is passed to impls of supertraits, i.e.
+// `impl<T> Named for Friend<T> where T: Named {}`.
+#[thin_delegate::fill_delegate(with_supertraits = true, infer_bounds = true)]
+impl<T> Greeter for Friend<T> {}
+
+struct NotHello;
+
+fn main() {
+    let wrapper = Wrapper("Alice".to_string());
+    assert_eq!(wrapper.hello(), "hello, Alice");
+    assert_eq!(Convert::<usize>::convert(&wrapper), 5);
+
+    let tagged = Tagged::<String, NotHello> {
+        inner: Box::new("Bob".to_string()),
+        marker: PhantomData,
+    };
+    assert_eq!(tagged.hello(), "hello, Bob");
+
+    let either = Either::<String, Wrapper<String>>::Right(Wrapper("Carol".to_string()));
+    assert_eq!(either.hello(), "hello, Carol");
+    assert_eq!(Either::<String, String>::Fixed("Dave".to_string()).hello(), "hello, Dave");
+
+    assert_eq!(Legacy(42u32).hello(), "hello, #42");
+
+    let friend = Friend("Erin".to_string());
+    assert_eq!(friend.greet(), "hi, Erin");
+    assert_eq!(friend.name(), "Erin");
+}
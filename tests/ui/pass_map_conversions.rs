@@ -0,0 +1,105 @@
+mod inner {
+    #[derive(Debug, PartialEq)]
+    pub struct InnerError(pub String);
+
+    pub trait LegacyStore {
+        fn get(&self, key: String) -> Result<u64, InnerError>;
+        fn len(&self) -> u32;
+    }
+
+    pub struct MemStore;
+
+    impl LegacyStore for MemStore {
+        fn get(&self, key: String) -> Result<u64, InnerError> {
+            match key.as_str() {
+                "one" => Ok(1),
+                _ => Err(InnerError(key)),
+            }
+        }
+
+        fn len(&self) -> u32 {
+            1
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum StoreError {
+    Inner(inner::InnerError),
+}
+
+impl From<inner::InnerError> for StoreError {
+    fn from(e: inner::InnerError) -> Self {
+        StoreError::Inner(e)
+    }
+}
+
+#[thin_delegate::register]
+pub trait Store {
+    fn get(&self, key: &str) -> Result<u64, StoreError>;
+    fn len(&self) -> u64;
+}
+
+#[thin_delegate::register]
+struct Cache(inner::MemStore);
+
+// `map_err` only applies to methods returning `Result`, and `map_return` applies to all methods.
+#[thin_delegate::fill_delegate(
+    to_trait = inner::LegacyStore,
+    map_arg(key = Into::into),
+    map_err = From::from,
+    map_return = Into::into,
+)]
+impl Store for Cache {}
+
+#[thin_delegate::register]
+enum Backend {
+    Mem(inner::MemStore),
+}
+
+#[thin_delegate::fill_delegate(
+    to_trait = inner::LegacyStore,
+    map_arg(key = |key: &str| key.to_lowercase()),
+    map_err = StoreError::Inner,
+    map_return = Into::into,
+)]
+impl Store for Backend {}
+
+#[thin_delegate::register]
+struct Wrapper {
+    store: inner::MemStore,
+}
+
+// Conversions also apply to schemes.
+#[thin_delegate::fill_delegate(
+    to_trait = inner::LegacyStore,
+    scheme = |f| f(&self.store),
+    map_arg(key = str::to_string),
+    map_err = From::from,
+    only(get),
+)]
+impl Store for Wrapper {
+    fn len(&self) -> u64 {
+        0
+    }
+}
+
+fn main() {
+    let cache = Cache(inner::MemStore);
+    assert_eq!(cache.get("one"), Ok(1));
+    assert_eq!(
+        cache.get("two"),
+        Err(StoreError::Inner(inner::InnerError("two".to_string())))
+    );
+    assert_eq!(cache.len(), 1);
+
+    let backend = Backend::Mem(inner::MemStore);
+    assert_eq!(backend.get("ONE"), Ok(1));
+    assert_eq!(backend.len(), 1);
+
+    let wrapper = Wrapper {
+        store: inner::MemStore,
+    };
+    assert_eq!(wrapper.get("one"), Ok(1));
+    assert_eq!(wrapper.len(), 0);
+}
@@ -0,0 +1,81 @@
+#[thin_delegate::register]
+trait Hello {
+    fn hello(&self) -> String;
+    fn bye(&self) -> String;
+    fn greet(&self) -> String {
+        format!("{} {}", self.hello(), self.bye())
+    }
+}
+
+impl Hello for String {
+    fn hello(&self) -> String {
+        format!("hello, {self}")
+    }
+
+    fn bye(&self) -> String {
+        format!("bye, {self}")
+    }
+
+    fn greet(&self) -> String {
+        format!("greet, {self}")
+    }
+}
+
+#[thin_delegate::register]
+struct Hoge(String);
+
+macro_rules! gen_bye {
+    ($self:ident, $body:tt) => {
+        fn bye(&$self) -> String $body
+    }
+}
+
+// `skip` allows to implement methods by macros.
+#[thin_delegate::fill_delegate(skip(bye))]
+impl Hello for Hoge {
+    gen_bye! {self, {
+        self.0.bye().to_uppercase()
+    }}
+}
+
+#[thin_delegate::register]
+struct Fuga(String);
+
+// Methods in `only` are filled even if they have default implementation.
+#[thin_delegate::fill_delegate(only(hello, greet))]
+impl Hello for Fuga {
+    fn bye(&self) -> String {
+        "see you".to_string()
+    }
+}
+
+#[thin_delegate::register]
+struct Piyo(String);
+
+// `greet` is left to the default implementation even with `delegate_fn_with_default_impl`.
+#[thin_delegate::fill_delegate(delegate_fn_with_default_impl = true, skip(greet))]
+impl Hello for Piyo {}
+
+#[thin_delegate::register]
+struct Hogera(String);
+
+#[thin_delegate::fill_delegate(with_default_impl(greet))]
+impl Hello for Hogera {}
+
+fn main() {
+    let hoge = Hoge("Alice".to_string());
+    assert_eq!(hoge.hello(), "hello, Alice");
+    assert_eq!(hoge.bye(), "BYE, ALICE");
+    assert_eq!(hoge.greet(), "hello, Alice BYE, ALICE");
+
+    let fuga = Fuga("Bob".to_string());
+    assert_eq!(fuga.hello(), "hello, Bob");
+    assert_eq!(fuga.bye(), "see you");
+    assert_eq!(fuga.greet(), "greet, Bob");
+
+    let piyo = Piyo("Charlie".to_string());
+    assert_eq!(piyo.greet(), "hello, Charlie bye, Charlie");
+
+    let hogera = Hogera("Dave".to_string());
+    assert_eq!(hogera.greet(), "greet, Dave");
+}
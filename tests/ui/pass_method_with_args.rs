@@ -26,10 +26,39 @@ enum Hoge {
 #[thin_delegate::fill_delegate]
 impl Hello for Hoge {}
 
+// Arguments that are not identifiers are also passed.
+#[thin_delegate::register]
+pub trait Underscore {
+    fn first(&self, _: u32) -> u32;
+    fn sum(&self, (a, b): (u32, u32)) -> u32 {
+        a + b
+    }
+}
+
+impl Underscore for u32 {
+    fn first(&self, x: u32) -> u32 {
+        self + x
+    }
+
+    fn sum(&self, (a, b): (u32, u32)) -> u32 {
+        self * (a + b)
+    }
+}
+
+#[thin_delegate::register]
+struct Fuga(u32);
+
+#[thin_delegate::fill_delegate(with_default_impl(sum))]
+impl Underscore for Fuga {}
+
 fn main() {
     let hoge = Hoge::A("a".to_string());
     assert_eq!(hoge.hello("hello, "), "hello, a");
 
     let hoge = Hoge::B('b');
     assert_eq!(hoge.hello("hello, "), "hello, b");
+
+    let fuga = Fuga(2);
+    assert_eq!(fuga.first(3), 5);
+    assert_eq!(fuga.sum((3, 4)), 14);
 }
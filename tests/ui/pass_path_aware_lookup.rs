@@ -0,0 +1,47 @@
+// `#[thin_delegate::fill_delegate]` searches registrations by the paths written in
+// `impl path::to::Trait for path::to::StructEnum`. So impls can live in a separate module without
+// importing anything.
+
+mod focus {
+    #[thin_delegate::register]
+    pub trait KeyboardTarget {
+        fn enter(&self, serial: usize) -> String;
+    }
+
+    impl KeyboardTarget for String {
+        fn enter(&self, serial: usize) -> String {
+            format!("{self}: enter {serial}")
+        }
+    }
+}
+
+mod window {
+    #[thin_delegate::register]
+    pub struct Window(pub String);
+
+    pub mod popup {
+        #[thin_delegate::register]
+        pub enum Popup {
+            Xdg(String),
+            Input(String),
+        }
+    }
+}
+
+mod delegations {
+    #[thin_delegate::fill_delegate]
+    impl crate::focus::KeyboardTarget for crate::window::Window {}
+
+    #[thin_delegate::fill_delegate]
+    impl super::focus::KeyboardTarget for super::window::popup::Popup {}
+}
+
+fn main() {
+    use focus::KeyboardTarget;
+
+    let window = window::Window("window".to_string());
+    assert_eq!(window.enter(1), "window: enter 1");
+
+    let popup = window::popup::Popup::Xdg("popup".to_string());
+    assert_eq!(popup.enter(2), "popup: enter 2");
+}
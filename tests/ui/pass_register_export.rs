@@ -0,0 +1,52 @@
+// `#[thin_delegate::register(export)]` makes registrations available from other crates.
+//
+// `#[thin_delegate::fill_delegate]` finds registrations by the paths of the trait and the
+// struct/enum, so exported ones can be used without importing anything.
+
+pub mod core_api {
+    pub struct Arg;
+
+    #[thin_delegate::register(export)]
+    pub trait Hello {
+        fn hello(&self, arg: crate::core_api::Arg) -> String;
+    }
+
+    impl Hello for String {
+        fn hello(&self, _arg: crate::core_api::Arg) -> String {
+            format!("hello, {self}")
+        }
+    }
+
+    #[thin_delegate::register(export)]
+    pub enum Hoge {
+        A(String),
+        B(String),
+    }
+}
+
+// Registrations with the same name in different modules don't conflict.
+pub mod other_api {
+    #[thin_delegate::register(export)]
+    pub trait Hello {
+        fn hello(&self) -> String;
+    }
+}
+
+#[thin_delegate::register]
+struct Fuga(String);
+
+#[thin_delegate::fill_delegate]
+impl core_api::Hello for Fuga {}
+
+#[thin_delegate::fill_delegate]
+impl core_api::Hello for core_api::Hoge {}
+
+fn main() {
+    use core_api::{Arg, Hello, Hoge};
+
+    let fuga = Fuga("fuga".to_string());
+    assert_eq!(fuga.hello(Arg), "hello, fuga");
+
+    let hoge = Hoge::A("a".to_string());
+    assert_eq!(hoge.hello(Arg), "hello, a");
+}
@@ -0,0 +1,58 @@
+// Registrations exported by `#[thin_delegate::register(export)]` in another crate
+// (tests/export_dep) can be used by their paths.
+
+use thin_delegate_export_dep::core_api;
+
+#[thin_delegate::register]
+struct Fuga(String);
+
+#[thin_delegate::fill_delegate]
+impl core_api::ExportedHello for Fuga {}
+
+#[thin_delegate::register]
+trait Area {
+    fn area(&self) -> f64;
+}
+
+impl Area for core_api::ExportedCircle {
+    fn area(&self) -> f64 {
+        3.0 * self.r * self.r
+    }
+}
+
+impl Area for core_api::ExportedSquare {
+    fn area(&self) -> f64 {
+        self.a * self.a
+    }
+}
+
+#[thin_delegate::register]
+trait Greet {
+    fn greet(&self) -> String;
+}
+
+impl Greet for String {
+    fn greet(&self) -> String {
+        format!("hi, {self}")
+    }
+}
+
+#[thin_delegate::fill_delegate]
+impl Area for core_api::ExportedShape {}
+
+#[thin_delegate::fill_delegate]
+impl Greet for core_api::ExportedName {}
+
+fn main() {
+    use core_api::{
+        ExportedArg, ExportedCircle, ExportedHello, ExportedName, ExportedShape, ExportedSquare,
+    };
+
+    let fuga = Fuga("fuga".to_string());
+    assert_eq!(fuga.hello(ExportedArg), "hello, fuga");
+
+    assert_eq!(ExportedShape::Circle(ExportedCircle { r: 1.0 }).area(), 3.0);
+    assert_eq!(ExportedShape::Square(ExportedSquare { a: 2.0 }).area(), 4.0);
+
+    assert_eq!(ExportedName("Alice".to_string()).greet(), "hi, Alice");
+}
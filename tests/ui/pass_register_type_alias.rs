@@ -0,0 +1,102 @@
+#[thin_delegate::register]
+pub trait Hello {
+    fn hello(&self) -> String;
+}
+
+impl Hello for String {
+    fn hello(&self) -> String {
+        format!("hello, {self}")
+    }
+}
+
+impl Hello for char {
+    fn hello(&self) -> String {
+        format!("hello, {self}")
+    }
+}
+
+impl<T: Hello> Hello for Box<T> {
+    fn hello(&self) -> String {
+        format!("boxed {}", self.as_ref().hello())
+    }
+}
+
+#[thin_delegate::register]
+pub enum Element<R> {
+    Name(String),
+    Inner(Box<R>),
+}
+
+// Generic arguments are substituted into the field types.
+#[thin_delegate::register]
+pub type CharElement = Element<char>;
+
+#[thin_delegate::fill_delegate]
+impl Hello for CharElement {}
+
+// Aliases of aliases are also allowed.
+#[thin_delegate::register]
+pub type MyElement = CharElement;
+
+#[thin_delegate::register]
+pub trait Len {
+    fn len(&self) -> usize;
+}
+
+impl Len for String {
+    fn len(&self) -> usize {
+        self.len()
+    }
+}
+
+impl Len for Box<char> {
+    fn len(&self) -> usize {
+        1
+    }
+}
+
+#[thin_delegate::fill_delegate]
+impl Len for MyElement {}
+
+#[thin_delegate::register]
+pub struct Wrapper<T>(T);
+
+#[thin_delegate::register]
+pub type Wrapped<'a> = Wrapper<&'a String>;
+
+impl Hello for &String {
+    fn hello(&self) -> String {
+        format!("ref {}", (*self).hello())
+    }
+}
+
+#[thin_delegate::fill_delegate]
+impl<'a> Hello for Wrapped<'a> {}
+
+mod other {
+    // Note that the path to the struct/enum must be valid where `fill_delegate` is used.
+    #[thin_delegate::register]
+    pub type Alias = crate::Element<String>;
+}
+
+#[thin_delegate::fill_delegate]
+impl Hello for other::Alias {}
+
+fn main() {
+    let x = CharElement::Name("Alice".to_string());
+    assert_eq!(x.hello(), "hello, Alice");
+    let x = CharElement::Inner(Box::new('a'));
+    assert_eq!(x.hello(), "boxed hello, a");
+
+    let x: MyElement = Element::Inner(Box::new('b'));
+    assert_eq!(x.hello(), "boxed hello, b");
+    assert_eq!(x.len(), 1);
+    let x: MyElement = Element::Name("Dave".to_string());
+    assert_eq!(x.len(), 4);
+
+    let s = "Bob".to_string();
+    assert_eq!(Wrapper(&s).hello(), "ref hello, Bob");
+
+    let x = other::Alias::Inner(Box::new("Carol".to_string()));
+    assert_eq!(x.hello(), "boxed hello, Carol");
+}
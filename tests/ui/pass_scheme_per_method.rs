@@ -0,0 +1,61 @@
+// `scheme(method, ...) = ...` applies to the designated methods, and `scheme = ...` applies to the
+// rest.
+
+#[thin_delegate::register]
+pub trait Hello {
+    fn hello(&self) -> String;
+    fn enter(&self) -> String;
+    fn leave(&mut self) -> String;
+}
+
+impl Hello for String {
+    fn hello(&self) -> String {
+        format!("hello, {self}")
+    }
+
+    fn enter(&self) -> String {
+        format!("enter {self}")
+    }
+
+    fn leave(&mut self) -> String {
+        format!("leave {self}")
+    }
+}
+
+#[thin_delegate::register]
+struct Window {
+    title: String,
+    surface: String,
+}
+
+#[thin_delegate::fill_delegate(
+    scheme(enter, leave) = |f| f(&mut self.surface.clone()),
+    scheme = |f| f(&self.title),
+)]
+impl Hello for Window {}
+
+#[thin_delegate::register]
+struct Popup {
+    surface: String,
+}
+
+// Without the default scheme, the rest are filled as usual.
+#[thin_delegate::fill_delegate(scheme(hello) = |f| f(&format!("popup of {}", self.surface)))]
+impl Hello for Popup {}
+
+fn main() {
+    let mut window = Window {
+        title: "Alice".to_string(),
+        surface: "surface".to_string(),
+    };
+    assert_eq!(window.hello(), "hello, Alice");
+    assert_eq!(window.enter(), "enter surface");
+    assert_eq!(window.leave(), "leave surface");
+
+    let mut popup = Popup {
+        surface: "Bob".to_string(),
+    };
+    assert_eq!(popup.hello(), "hello, popup of Bob");
+    assert_eq!(popup.enter(), "enter Bob");
+    assert_eq!(popup.leave(), "leave Bob");
+}
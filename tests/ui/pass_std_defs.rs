@@ -0,0 +1,78 @@
+use std::fmt::{Debug, Display};
+use std::hash::{Hash, Hasher};
+use std::io::Write;
+
+#[thin_delegate::register]
+struct Name(String);
+
+#[thin_delegate::fill_delegate(external_trait_def = thin_delegate::std_defs::fmt)]
+impl Display for Name {}
+
+#[thin_delegate::fill_delegate(external_trait_def = thin_delegate::std_defs::fmt)]
+impl Debug for Name {}
+
+#[thin_delegate::fill_delegate(external_trait_def = thin_delegate::std_defs::convert)]
+impl AsRef<str> for Name {}
+
+#[thin_delegate::fill_delegate(external_trait_def = thin_delegate::std_defs::hash)]
+impl Hash for Name {}
+
+// Functions without receiver, e.g. `Hash::hash_slice()`, are not filled by
+// `delegate_fn_with_default_impl`.
+#[thin_delegate::register]
+struct Key(u64);
+
+#[thin_delegate::fill_delegate(
+    external_trait_def = thin_delegate::std_defs::hash,
+    delegate_fn_with_default_impl = true
+)]
+impl Hash for Key {}
+
+#[thin_delegate::register]
+struct Buffer {
+    inner: Vec<u8>,
+}
+
+#[thin_delegate::fill_delegate(external_trait_def = ::thin_delegate::std_defs::io)]
+impl Write for Buffer {}
+
+#[thin_delegate::register]
+enum Error {
+    Io(std::io::Error),
+    Fmt(std::fmt::Error),
+}
+
+#[thin_delegate::fill_delegate(external_trait_def = thin_delegate::std_defs::fmt)]
+impl Display for Error {}
+
+#[thin_delegate::fill_delegate(external_trait_def = thin_delegate::std_defs::fmt)]
+impl Debug for Error {}
+
+#[thin_delegate::fill_delegate(external_trait_def = thin_delegate::std_defs::error)]
+impl std::error::Error for Error {}
+
+fn hash_of<T: Hash>(x: &T) -> u64 {
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    x.hash(&mut hasher);
+    hasher.finish()
+}
+
+fn main() {
+    let name = Name("Alice".to_string());
+    assert_eq!(format!("{name}"), "Alice");
+    assert_eq!(format!("{name:?}"), "\"Alice\"");
+    assert_eq!(name.as_ref(), "Alice");
+    assert_eq!(hash_of(&name), hash_of(&"Alice".to_string()));
+    assert_eq!(hash_of(&Key(42)), hash_of(&42_u64));
+
+    let mut buffer = Buffer { inner: vec![] };
+    write!(buffer, "{}-{}", 1, 2).unwrap();
+    buffer.flush().unwrap();
+    assert_eq!(buffer.inner, b"1-2");
+
+    let e = Error::Io(std::io::Error::new(std::io::ErrorKind::Other, "oops"));
+    assert_eq!(format!("{e}"), "oops");
+    assert!(std::error::Error::source(&e).is_none());
+    let e = Error::Fmt(std::fmt::Error);
+    assert_eq!(format!("{e}"), "an error occurred when formatting an argument");
+}
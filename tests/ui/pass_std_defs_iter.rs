@@ -0,0 +1,99 @@
+use std::cell::Cell;
+
+// An iterator that counts calls of `nth()` to check that it is delegated.
+struct Counting<'a> {
+    n: u32,
+    end: u32,
+    nth_called: &'a Cell<usize>,
+}
+
+impl Iterator for Counting<'_> {
+    type Item = u32;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.n < self.end {
+            self.n += 1;
+            Some(self.n - 1)
+        } else {
+            None
+        }
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.nth_called.set(self.nth_called.get() + 1);
+        self.n += n as u32;
+        self.next()
+    }
+}
+
+#[thin_delegate::register]
+struct Wrapper<I>(I);
+
+#[thin_delegate::fill_delegate(external_trait_def = thin_delegate::std_defs::iter)]
+impl<I: Iterator> Iterator for Wrapper<I> {}
+
+#[thin_delegate::fill_delegate(external_trait_def = thin_delegate::std_defs::iter)]
+impl<I: DoubleEndedIterator> DoubleEndedIterator for Wrapper<I> {}
+
+#[thin_delegate::fill_delegate(external_trait_def = thin_delegate::std_defs::iter)]
+impl<I: ExactSizeIterator> ExactSizeIterator for Wrapper<I> {}
+
+#[thin_delegate::register]
+struct Bytes<'a> {
+    inner: std::slice::Iter<'a, u8>,
+}
+
+#[thin_delegate::fill_delegate(external_trait_def = thin_delegate::std_defs::iter)]
+impl<'a> Iterator for Bytes<'a> {
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|x| x)
+    }
+}
+
+#[thin_delegate::register]
+enum Either<L, R> {
+    Left(L),
+    Right(R),
+}
+
+// For enum, you need to fill `type Item`.
+#[thin_delegate::fill_delegate(external_trait_def = thin_delegate::std_defs::iter)]
+impl<T, L: Iterator<Item = T>, R: Iterator<Item = T>> Iterator for Either<L, R> {
+    type Item = T;
+}
+
+fn main() {
+    let nth_called = Cell::new(0);
+    let mut it = Wrapper(Counting {
+        n: 0,
+        end: 10,
+        nth_called: &nth_called,
+    });
+    assert_eq!(it.nth(3), Some(3));
+    assert_eq!(nth_called.get(), 1);
+    assert_eq!(it.next(), Some(4));
+    assert_eq!(it.size_hint(), (0, None));
+
+    let mut it = Wrapper(vec![1, 2, 3, 4].into_iter());
+    assert_eq!(it.len(), 4);
+    assert_eq!(it.next_back(), Some(4));
+    assert_eq!(it.size_hint(), (3, Some(3)));
+    assert_eq!(it.rev().collect::<Vec<_>>(), vec![3, 2, 1]);
+    assert_eq!(Wrapper(1..5).fold(0, |acc, x| acc + x), 10);
+    assert_eq!(Wrapper(1..5).rfold(0, |acc, x| acc * 2 + x), 49);
+    assert_eq!(Wrapper(1..5).count(), 4);
+    assert_eq!(Wrapper(1..5).last(), Some(4));
+    assert_eq!(Wrapper(1..5).map(|x| x * 2).sum::<i32>(), 20);
+
+    let bytes = [1u8, 2, 3];
+    let it = Bytes {
+        inner: bytes.iter(),
+    };
+    assert_eq!(it.size_hint(), (3, Some(3)));
+    assert_eq!(it.copied().collect::<Vec<_>>(), vec![1, 2, 3]);
+
+    let it: Either<std::ops::Range<u8>, std::vec::IntoIter<u8>> = Either::Left(0..3);
+    assert_eq!(it.collect::<Vec<_>>(), vec![0, 1, 2]);
+    let it: Either<std::ops::Range<u8>, std::vec::IntoIter<u8>> = Either::Right(vec![5].into_iter());
+    assert_eq!(it.count(), 1);
+}
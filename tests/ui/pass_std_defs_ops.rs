@@ -0,0 +1,94 @@
+use std::marker::PhantomData;
+use std::ops::{Add, AddAssign, Div, Mul, Neg, Not, Sub};
+
+#[thin_delegate::register]
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Meters(f64);
+
+#[thin_delegate::fill_delegate(external_trait_def = thin_delegate::std_defs::ops)]
+impl Add for Meters {}
+
+#[thin_delegate::fill_delegate(external_trait_def = thin_delegate::std_defs::ops)]
+impl Sub for Meters {}
+
+#[thin_delegate::fill_delegate(external_trait_def = thin_delegate::std_defs::ops)]
+impl Neg for Meters {}
+
+#[thin_delegate::fill_delegate(external_trait_def = thin_delegate::std_defs::ops)]
+impl AddAssign for Meters {}
+
+// By reference.
+#[thin_delegate::fill_delegate(external_trait_def = thin_delegate::std_defs::ops)]
+impl Add for &Meters {}
+
+#[thin_delegate::fill_delegate(external_trait_def = thin_delegate::std_defs::ops)]
+impl<'a> Add<&'a Meters> for Meters {}
+
+// `rhs` that is not the struct is passed as is.
+#[thin_delegate::fill_delegate(external_trait_def = thin_delegate::std_defs::ops)]
+impl Mul<f64> for Meters {}
+
+// If `type Output` is given, output of inner type is converted with `Into::into()`.
+#[thin_delegate::fill_delegate(external_trait_def = thin_delegate::std_defs::ops)]
+impl Div for Meters {
+    type Output = f64;
+}
+
+#[thin_delegate::fill_delegate(external_trait_def = thin_delegate::std_defs::ops)]
+impl Mul for Meters {
+    type Output = SquareMeters;
+}
+
+#[derive(Debug, PartialEq)]
+struct SquareMeters(f64);
+
+impl From<f64> for SquareMeters {
+    fn from(x: f64) -> Self {
+        SquareMeters(x)
+    }
+}
+
+// Generics and named field.
+#[thin_delegate::register]
+#[derive(Debug, PartialEq)]
+struct Flags<T> {
+    bits: T,
+}
+
+#[thin_delegate::fill_delegate(external_trait_def = thin_delegate::std_defs::ops)]
+impl<T: Not<Output = T>> Not for Flags<T> {}
+
+// Marker fields are filled with `Default::default()`.
+#[derive(Debug, PartialEq)]
+struct Km;
+
+#[thin_delegate::register]
+#[derive(Debug, PartialEq)]
+struct Length<U>(f64, PhantomData<U>);
+
+#[thin_delegate::fill_delegate(external_trait_def = thin_delegate::std_defs::ops)]
+impl<U> Add for Length<U> {}
+
+fn main() {
+    let a = Meters(1.5);
+    let b = Meters(2.0);
+    assert_eq!(a + b, Meters(3.5));
+    assert_eq!(b - a, Meters(0.5));
+    assert_eq!(-a, Meters(-1.5));
+    assert_eq!(&a + &b, Meters(3.5));
+    assert_eq!(a + &b, Meters(3.5));
+    assert_eq!(a * 2.0, Meters(3.0));
+    assert_eq!(b / a, 2.0 / 1.5);
+    assert_eq!(a * b, SquareMeters(3.0));
+
+    let mut c = a;
+    c += b;
+    assert_eq!(c, Meters(3.5));
+
+    assert_eq!(!Flags { bits: 0b0101u8 }, Flags { bits: 0b1111_1010 });
+
+    assert_eq!(
+        Length::<Km>(1.0, PhantomData) + Length(2.0, PhantomData),
+        Length(3.0, PhantomData)
+    );
+}
@@ -0,0 +1,111 @@
+mod other {
+    #[thin_delegate::register]
+    pub trait RenderLegacy {
+        fn draw_frame(&self, scale: u32) -> String;
+        fn resize(&mut self, width: u32);
+    }
+}
+
+#[thin_delegate::register]
+pub trait Render {
+    fn render(&self, scale: u32) -> String;
+    fn resize(&mut self, width: u32);
+}
+
+pub struct Canvas {
+    width: u32,
+}
+
+impl other::RenderLegacy for Canvas {
+    fn draw_frame(&self, scale: u32) -> String {
+        format!("canvas {}", self.width * scale)
+    }
+
+    fn resize(&mut self, width: u32) {
+        self.width = width;
+    }
+}
+
+pub struct Svg;
+
+impl other::RenderLegacy for Svg {
+    fn draw_frame(&self, scale: u32) -> String {
+        format!("svg x{scale}")
+    }
+
+    fn resize(&mut self, _width: u32) {}
+}
+
+#[thin_delegate::register]
+struct Window(Canvas);
+
+#[thin_delegate::fill_delegate(
+    to_trait = other::RenderLegacy,
+    rename(render = draw_frame),
+    verify_to_trait = true,
+)]
+impl Render for Window {}
+
+#[thin_delegate::register]
+enum Surface {
+    Canvas(Canvas),
+    Svg(Svg),
+}
+
+// The definition of `to_trait` is not necessary without `verify_to_trait = true`.
+#[thin_delegate::fill_delegate(to_trait = other::RenderLegacy, rename(render = draw_frame))]
+impl Render for Surface {}
+
+#[thin_delegate::register]
+pub trait Hello {
+    fn hello(&self) -> String;
+    fn bye(&self) -> String;
+}
+
+impl Hello for String {
+    fn hello(&self) -> String {
+        format!("hello, {self}")
+    }
+
+    fn bye(&self) -> String {
+        format!("bye, {self}")
+    }
+}
+
+#[thin_delegate::register]
+struct Hoge(String);
+
+// `rename` works within a trait.
+#[thin_delegate::fill_delegate(rename(hello = bye))]
+impl Hello for Hoge {}
+
+#[thin_delegate::register]
+struct Fuga {
+    name: String,
+}
+
+// Schemes also call renamed methods.
+#[thin_delegate::fill_delegate(rename(bye = hello), scheme = |f| f(&self.name))]
+impl Hello for Fuga {}
+
+fn main() {
+    let mut window = Window(Canvas { width: 10 });
+    assert_eq!(window.render(2), "canvas 20");
+    window.resize(5);
+    assert_eq!(window.render(2), "canvas 10");
+
+    let surface = Surface::Canvas(Canvas { width: 3 });
+    assert_eq!(surface.render(3), "canvas 9");
+    let surface = Surface::Svg(Svg);
+    assert_eq!(surface.render(3), "svg x3");
+
+    let hoge = Hoge("Alice".to_string());
+    assert_eq!(hoge.hello(), "bye, Alice");
+    assert_eq!(hoge.bye(), "bye, Alice");
+
+    let fuga = Fuga {
+        name: "Bob".to_string(),
+    };
+    assert_eq!(fuga.hello(), "hello, Bob");
+    assert_eq!(fuga.bye(), "hello, Bob");
+}
@@ -0,0 +1,86 @@
+// Unit variants are handled by `unit_variants = unreachable|panic|default` or
+// `#[thin_delegate::unit_variant(...)]` on the variant, which gives an expression for all methods
+// or per method.
+
+#[thin_delegate::register]
+pub trait Hello {
+    fn hello(&self) -> String;
+    fn len(&self) -> usize;
+}
+
+impl Hello for String {
+    fn hello(&self) -> String {
+        format!("hello, {self}")
+    }
+
+    fn len(&self) -> usize {
+        String::len(self)
+    }
+}
+
+#[thin_delegate::register]
+enum Focus {
+    None,
+    Window(String),
+    Layer { name: String },
+}
+
+#[thin_delegate::fill_delegate(unit_variants = default)]
+impl Hello for Focus {}
+
+#[thin_delegate::register]
+enum Target {
+    Window(String),
+    #[thin_delegate::unit_variant(hello = "nobody".into(), len = 0)]
+    Nobody,
+    #[thin_delegate::unit_variant(hello = "empty".into())]
+    Empty {},
+}
+
+#[thin_delegate::fill_delegate(unit_variants = panic)]
+impl Hello for Target {}
+
+#[thin_delegate::register]
+enum Anonymous {
+    Named(String),
+    #[thin_delegate::unit_variant(Default::default())]
+    Anonymous,
+}
+
+#[thin_delegate::fill_delegate]
+impl Hello for Anonymous {}
+
+#[thin_delegate::register]
+enum NeverUnit {
+    #[allow(unused)]
+    Unit(),
+    Window(String),
+}
+
+#[thin_delegate::fill_delegate(unit_variants = unreachable)]
+impl Hello for NeverUnit {}
+
+fn main() {
+    assert_eq!(Focus::None.hello(), "");
+    assert_eq!(Focus::None.len(), 0);
+    assert_eq!(Focus::Window("Alice".to_string()).hello(), "hello, Alice");
+    assert_eq!(
+        Focus::Layer {
+            name: "Bob".to_string()
+        }
+        .len(),
+        3
+    );
+
+    assert_eq!(Target::Nobody.hello(), "nobody");
+    assert_eq!(Target::Nobody.len(), 0);
+    assert_eq!(Target::Empty {}.hello(), "empty");
+    std::panic::set_hook(Box::new(|_| {}));
+    let result = std::panic::catch_unwind(|| Target::Empty {}.len());
+    assert!(result.is_err());
+
+    assert_eq!(Anonymous::Anonymous.hello(), "");
+    assert_eq!(Anonymous::Anonymous.len(), 0);
+
+    assert_eq!(NeverUnit::Window("Carol".to_string()).len(), 5);
+}
@@ -0,0 +1,140 @@
+mod alive {
+    #[thin_delegate::register]
+    pub trait IsAlive {
+        fn alive(&self) -> bool;
+    }
+}
+
+use alive::IsAlive;
+
+#[thin_delegate::register]
+pub trait Named {
+    fn name(&self) -> String;
+}
+
+// Auto traits, derivable traits and `std` traits not bundled in `thin_delegate::std_defs` are
+// skipped.
+#[thin_delegate::register]
+pub trait KeyboardTarget<D>:
+    alive::IsAlive + Named + PartialEq + Clone + std::fmt::Debug + Send + std::fmt::Display
+{
+    fn enter(&self, data: &mut D);
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Surface(String);
+
+impl IsAlive for Surface {
+    fn alive(&self) -> bool {
+        !self.0.is_empty()
+    }
+}
+
+impl Named for Surface {
+    fn name(&self) -> String {
+        self.0.clone()
+    }
+}
+
+impl std::fmt::Display for Surface {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "surface {}", self.0)
+    }
+}
+
+impl KeyboardTarget<Vec<String>> for Surface {
+    fn enter(&self, data: &mut Vec<String>) {
+        data.push(self.0.clone());
+    }
+}
+
+#[thin_delegate::register]
+#[derive(Clone, Debug, PartialEq)]
+pub enum Target {
+    Surface(Surface),
+}
+
+#[thin_delegate::fill_delegate(with_supertraits = true)]
+impl KeyboardTarget<Vec<String>> for Target {}
+
+#[thin_delegate::register]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Wrapper<T>(T);
+
+#[thin_delegate::fill_delegate(with_supertraits = true)]
+impl<T> KeyboardTarget<Vec<String>> for Wrapper<T>
+where
+    T: KeyboardTarget<Vec<String>>,
+{
+}
+
+// Bare names of traits bundled in `thin_delegate::std_defs` are regarded as the ones in `std`.
+use std::fmt::Display;
+
+#[thin_delegate::register]
+pub trait Greeter: Display + Named {
+    fn greet(&self) -> String;
+}
+
+impl Greeter for Surface {
+    fn greet(&self) -> String {
+        format!("hello, {}", self.0)
+    }
+}
+
+#[thin_delegate::register]
+pub struct Nickname(Surface);
+
+impl Named for Nickname {
+    fn name(&self) -> String {
+        format!("nick {}", self.0.name())
+    }
+}
+
+// `Named` is implemented by hand.
+#[thin_delegate::fill_delegate(with_supertraits(skip(Named)))]
+impl Greeter for Nickname {}
+
+// Auto traits and derivable traits are filled by `include` if they are bundled in
+// `thin_delegate::std_defs` or registered.
+#[thin_delegate::register]
+pub trait Loggable: std::fmt::Debug + Named {
+    fn log(&self) -> String;
+}
+
+impl Loggable for Surface {
+    fn log(&self) -> String {
+        format!("{self:?}")
+    }
+}
+
+#[thin_delegate::register]
+pub struct Logged(Surface);
+
+#[thin_delegate::fill_delegate(with_supertraits(include(Debug)))]
+impl Loggable for Logged {}
+
+fn main() {
+    let target = Target::Surface(Surface("a".to_string()));
+    assert!(target.alive());
+    assert_eq!(target.name(), "a");
+    assert_eq!(target.to_string(), "surface a");
+    let mut data = vec![];
+    target.enter(&mut data);
+    assert_eq!(data, vec!["a".to_string()]);
+
+    let wrapper = Wrapper(Surface("b".to_string()));
+    assert!(wrapper.alive());
+    assert_eq!(wrapper.name(), "b");
+    assert_eq!(wrapper.to_string(), "surface b");
+
+    let nickname = Nickname(Surface("c".to_string()));
+    assert_eq!(nickname.greet(), "hello, c");
+    assert_eq!(nickname.name(), "nick c");
+    assert_eq!(nickname.to_string(), "surface c");
+
+    let logged = Logged(Surface("d".to_string()));
+    assert_eq!(format!("{logged:?}"), "Surface(\"d\")");
+    assert_eq!(logged.name(), "d");
+    assert_eq!(logged.log(), "Surface(\"d\")");
+}